@@ -0,0 +1,65 @@
+//Embed what --version reports about the build: the git commit, the build time and the enabled
+//features. It reruns when the sources or the commit change.
+
+use std::{
+    env,
+    path::PathBuf,
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+//Run git in the package directory, None if it fails.
+fn git(args: &[&str]) -> Option<String> {
+    let out = Command::new("git")
+        .args(args)
+        .current_dir(env::var_os("CARGO_MANIFEST_DIR")?)
+        .output()
+        .ok()
+        .filter(|x| x.status.success())?;
+    Some(String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
+
+fn main() {
+    //Only the package's own repository counts. A published crate extracted to registry/src has
+    //none, but can be inside an unrelated one, like a home directory kept in git.
+    let manifest_dir = env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from);
+    let toplevel = git(&["rev-parse", "--show-toplevel"]).map(PathBuf::from);
+    let own_repository = manifest_dir
+        .zip(toplevel)
+        .is_some_and(|(a, b)| a.canonicalize().ok() == b.canonicalize().ok());
+    //Empty when there's no repository of its own.
+    let mut commit = String::new();
+    if own_repository {
+        commit = git(&["rev-parse", "--short=12", "HEAD"]).unwrap_or_default();
+        //Committing changes HEAD or the branch it points to, but no source file.
+        if let Some(git_dir) = git(&["rev-parse", "--absolute-git-dir"]).map(PathBuf::from) {
+            for file in ["HEAD", "refs", "packed-refs"] {
+                println!("cargo:rerun-if-changed={}", git_dir.join(file).display());
+            }
+        }
+    }
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    //SOURCE_DATE_EPOCH keeps reproducible builds reproducible.
+    let time = env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|x| x.parse().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |x| x.as_secs())
+        });
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(key, _)| {
+            let feature = key.strip_prefix("CARGO_FEATURE_")?;
+            Some(feature.to_lowercase().replace('_', "-"))
+        })
+        .collect();
+    features.sort();
+
+    println!("cargo:rustc-env=BUILD_COMMIT={commit}");
+    println!("cargo:rustc-env=BUILD_TIME={time}");
+    println!("cargo:rustc-env=BUILD_FEATURES={}", features.join(","));
+}
@@ -0,0 +1,94 @@
+//Which crate a binary comes from when it's named differently, like rg from ripgrep. Cargo records
+//it in .crates.toml, but install roots made by hand or copied between systems often don't have it,
+//so a list of well-known crates fills in, and users can add their own.
+
+use crate::crates_toml::Installed;
+use std::{collections::HashMap, fs, path::PathBuf};
+
+//Binary and crate name, only for crates whose binaries aren't named after them.
+const BUILTIN: [(&str, &str); 24] = [
+    ("btm", "bottom"),
+    ("cargo-add", "cargo-edit"),
+    ("cargo-rm", "cargo-edit"),
+    ("cargo-set-version", "cargo-edit"),
+    ("cargo-sqlx", "sqlx-cli"),
+    ("cargo-upgrade", "cargo-edit"),
+    ("delta", "git-delta"),
+    ("diesel", "diesel_cli"),
+    ("difft", "difftastic"),
+    ("dua", "dua-cli"),
+    ("dust", "du-dust"),
+    ("fd", "fd-find"),
+    ("jj", "jj-cli"),
+    ("makers", "cargo-make"),
+    ("rg", "ripgrep"),
+    ("rga", "ripgrep_all"),
+    ("sk", "skim"),
+    ("spt", "spotify-tui"),
+    ("sqlx", "sqlx-cli"),
+    ("taplo", "taplo-cli"),
+    ("tldr", "tealdeer"),
+    ("typos", "typos-cli"),
+    ("wasm-bindgen", "wasm-bindgen-cli"),
+    ("watchexec", "watchexec-cli"),
+];
+
+pub fn path() -> Option<PathBuf> {
+    Some(crate::config_dir()?.join("aliases"))
+}
+
+//Aliases set by the user, as `bin = "crate"` lines in the config directory.
+pub fn load() -> Vec<(String, String)> {
+    let Some(path) = path() else {
+        return Vec::new();
+    };
+    fs::read_to_string(path)
+        .map(|x| crate::config::parse_pairs(&x))
+        .unwrap_or_default()
+}
+
+//Binary to crate name. What cargo recorded wins over the user's aliases, which win over the
+//built-in ones.
+pub fn resolve(recorded: &[Installed], custom: &[(String, String)]) -> HashMap<String, String> {
+    let mut aliases: HashMap<String, String> = BUILTIN
+        .iter()
+        .map(|(bin, name)| (bin.to_string(), name.to_string()))
+        .collect();
+    aliases.extend(custom.iter().cloned());
+    for x in recorded {
+        for bin in &x.bins {
+            aliases.insert(crate::paths::bin_name(bin).to_string(), x.name.clone());
+        }
+    }
+    aliases
+}
+
+mod test {
+    #[test]
+    fn resolve() {
+        let recorded = [crate::crates_toml::Installed {
+            name: String::from("ripgrep-fork"),
+            version: String::from("1.0.0"),
+            source: String::new(),
+            bins: vec![String::from("rg")],
+            features: Vec::new(),
+            no_default_features: false,
+            all_features: false,
+        }];
+        let custom = [
+            (String::from("fd"), String::from("my-fd")),
+            (String::from("tool"), String::from("my-tools")),
+        ];
+        let aliases = super::resolve(&recorded, &custom);
+        assert_eq!(aliases["rg"], "ripgrep-fork");
+        assert_eq!(aliases["fd"], "my-fd");
+        assert_eq!(aliases["tool"], "my-tools");
+        assert_eq!(aliases["delta"], "git-delta");
+        assert_eq!(aliases.get("just"), None);
+    }
+
+    #[test]
+    fn builtin_sorted() {
+        assert!(super::BUILTIN.windows(2).all(|x| x[0].0 < x[1].0));
+    }
+}
@@ -0,0 +1,92 @@
+//Information about how a binary was built, read from the binary itself without running it.
+
+use regex::bytes::Regex;
+use std::{fs, io::Read, path::Path, sync::OnceLock, time::SystemTime};
+
+//Binaries are read this much at a time, rather than whole, since some are hundreds of megabytes.
+const CHUNK: usize = 64 * 1024;
+//Kept from the end of the previous chunk, longer than any version string, so one split between two
+//chunks is still found.
+const OVERLAP: usize = 128;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct BuildInfo {
+    //Version of rustc that built the binary.
+    pub rustc: Option<String>,
+    //When the binary was written to the bin directory, which cargo install does right after
+    //building it.
+    pub built: Option<SystemTime>,
+}
+
+//rustc records itself in the .comment section of ELF binaries as e.g.
+//"rustc version 1.70.0 (90c541806 2023-05-31)". Other formats don't have it.
+//It's compiled once, not for every binary.
+fn rustc_version(binary: &[u8]) -> Option<String> {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| Regex::new(r"rustc version (\d+\.\d+\.\d+[-\w.]*)").unwrap());
+    let version = re.captures(binary)?.get(1)?;
+    //A version at the very end may go on in the next chunk.
+    if version.end() == binary.len() {
+        return None;
+    }
+    String::from_utf8(version.as_bytes().to_vec()).ok()
+}
+
+//Look for the version chunk by chunk.
+fn read_rustc_version(mut reader: impl Read) -> Option<String> {
+    let mut buffer = Vec::with_capacity(OVERLAP + CHUNK);
+    let mut chunk = vec![0; CHUNK];
+    loop {
+        let n = reader.read(&mut chunk).ok()?;
+        if n == 0 {
+            //Nothing follows the last chunk, so a version at its end is whole.
+            buffer.push(0);
+            return rustc_version(&buffer);
+        }
+        buffer.extend_from_slice(&chunk[..n]);
+        if let Some(version) = rustc_version(&buffer) {
+            return Some(version);
+        }
+        buffer.drain(..buffer.len().saturating_sub(OVERLAP));
+    }
+}
+
+pub fn read(path: &Path) -> BuildInfo {
+    BuildInfo {
+        rustc: fs::File::open(path).ok().and_then(read_rustc_version),
+        built: fs::metadata(path).and_then(|x| x.modified()).ok(),
+    }
+}
+
+mod test {
+    #[test]
+    fn rustc_version() {
+        let binary =
+            b"\x7fELF\0\0garbage\0rustc version 1.70.0 (90c541806 2023-05-31)\0Linker: LLD";
+        assert_eq!(super::rustc_version(binary), Some(String::from("1.70.0")));
+        let nightly = b"\0rustc version 1.72.0-nightly (871b59520 2023-05-31)\0";
+        assert_eq!(
+            super::rustc_version(nightly),
+            Some(String::from("1.72.0-nightly"))
+        );
+        assert_eq!(super::rustc_version(b"\0GCC: (Debian 12.2.0)\0"), None);
+    }
+
+    #[test]
+    fn read_rustc_version() {
+        //Split between two chunks.
+        let mut binary = vec![0; super::CHUNK - 20];
+        binary.extend_from_slice(b"rustc version 1.72.0-nightly (871b59520 2023-05-31)\0");
+        binary.extend(vec![0; super::CHUNK]);
+        assert_eq!(
+            super::read_rustc_version(binary.as_slice()),
+            Some(String::from("1.72.0-nightly"))
+        );
+        //At the very end.
+        assert_eq!(
+            super::read_rustc_version(&b"\0rustc version 1.70.0"[..]),
+            Some(String::from("1.70.0"))
+        );
+        assert_eq!(super::read_rustc_version(&[0u8; 10][..]), None);
+    }
+}
@@ -0,0 +1,122 @@
+//A shareable page listing the installed tools, what they do and where to learn more about them.
+
+use crate::PkgInfo;
+
+const TITLE: &str = "Installed tools";
+
+//Each crate with the binaries it provides, sorted by crate name.
+pub fn entries<'a>(installed: &[(&'a str, &'a PkgInfo)]) -> Vec<(&'a PkgInfo, Vec<&'a str>)> {
+    let mut entries: Vec<(&PkgInfo, Vec<&str>)> = Vec::new();
+    for (bin, info) in installed {
+        match entries.iter_mut().find(|(x, _)| x.name == info.name) {
+            Some((_, bins)) => bins.push(bin),
+            None => entries.push((info, vec![bin])),
+        }
+    }
+    entries.sort_by(|a, b| a.0.name.cmp(&b.0.name));
+    entries
+}
+
+//The homepage, or the crate's page on crates.io when there's none.
+fn link(info: &PkgInfo) -> String {
+    if info.homepage.is_empty() {
+        format!("https://crates.io/crates/{}", info.name)
+    } else {
+        info.homepage.clone()
+    }
+}
+
+fn escape_markdown(s: &str) -> String {
+    s.chars()
+        .flat_map(|x| match x {
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' => vec!['\\', x],
+            x => vec![x],
+        })
+        .collect()
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+//Binaries without metadata are listed by name at the end.
+pub fn markdown(entries: &[(&PkgInfo, Vec<&str>)], unknown: &[String]) -> String {
+    let mut out = format!("# {TITLE}\n\n");
+    for (info, bins) in entries {
+        let bins: Vec<String> = bins.iter().map(|x| format!("`{x}`")).collect();
+        out += &format!(
+            "- [{}]({}) {} - {}\n",
+            escape_markdown(&info.name),
+            link(info),
+            bins.join(" "),
+            escape_markdown(&info.description)
+        );
+    }
+    if !unknown.is_empty() {
+        let unknown: Vec<String> = unknown.iter().map(|x| format!("`{x}`")).collect();
+        out += &format!("\nAlso installed: {}\n", unknown.join(" "));
+    }
+    out
+}
+
+pub fn html(entries: &[(&PkgInfo, Vec<&str>)], unknown: &[String]) -> String {
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{TITLE}</title>\n</head>\n<body>\n<h1>{TITLE}</h1>\n<ul>\n"
+    );
+    for (info, bins) in entries {
+        let bins: Vec<String> = bins
+            .iter()
+            .map(|x| format!("<code>{}</code>", escape_html(x)))
+            .collect();
+        out += &format!(
+            "<li><a href=\"{}\">{}</a> {} - {}</li>\n",
+            escape_html(&link(info)),
+            escape_html(&info.name),
+            bins.join(" "),
+            escape_html(&info.description)
+        );
+    }
+    out += "</ul>\n";
+    if !unknown.is_empty() {
+        let unknown: Vec<String> = unknown
+            .iter()
+            .map(|x| format!("<code>{}</code>", escape_html(x)))
+            .collect();
+        out += &format!("<p>Also installed: {}</p>\n", unknown.join(" "));
+    }
+    out += "</body>\n</html>\n";
+    out
+}
+
+mod test {
+    #[test]
+    fn cheatsheet() {
+        let info = |name: &str, description: &str, homepage: &str| crate::PkgInfo {
+            name: name.to_string(),
+            description: description.to_string(),
+            homepage: homepage.to_string(),
+            ..Default::default()
+        };
+        let rg = info(
+            "ripgrep",
+            "Search <fast>",
+            "https://github.com/BurntSushi/ripgrep",
+        );
+        let just = info("just", "Run *recipes*", "");
+        let entries = super::entries(&[("rg", &rg), ("just", &just), ("rga", &rg)]);
+        let unknown = [String::from("other")];
+
+        assert_eq!(
+            super::markdown(&entries, &unknown),
+            "# Installed tools\n\n- [just](https://crates.io/crates/just) `just` - Run \\*recipes\\*\n- [ripgrep](https://github.com/BurntSushi/ripgrep) `rg` `rga` - Search \\<fast\\>\n\nAlso installed: `other`\n"
+        );
+        let html = super::html(&entries, &unknown);
+        assert!(html.contains("<li><a href=\"https://github.com/BurntSushi/ripgrep\">ripgrep</a> <code>rg</code> <code>rga</code> - Search &lt;fast&gt;</li>\n"));
+        assert!(
+            html.ends_with("</ul>\n<p>Also installed: <code>other</code></p>\n</body>\n</html>\n")
+        );
+    }
+}
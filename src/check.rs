@@ -0,0 +1,124 @@
+//Compare installed crates against a toolbox manifest, a TOML-like list of crates and versions:
+//
+//  [tools]
+//  ripgrep = "13"
+//  cargo-edit = "0.12.0"
+//  just = "*"
+//
+//A version matches when it's equal to the installed one or is a prefix of it ending at a dot,
+//"*" matches any version.
+
+use crate::i18n::{tr, Lang};
+use colored::Colorize;
+use std::collections::BTreeMap;
+
+fn version_matches(wanted: &str, installed: &str) -> bool {
+    wanted == "*"
+        || installed == wanted
+        || installed
+            .strip_prefix(wanted)
+            .is_some_and(|x| x.starts_with('.'))
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Report {
+    //Name and wanted version.
+    pub missing: Vec<(String, String)>,
+    //Name, installed and wanted version.
+    pub mismatched: Vec<(String, String, String)>,
+    //Name and installed version.
+    pub extra: Vec<(String, String)>,
+}
+
+impl Report {
+    //Extra crates are fine, everything the manifest asks for has to be there.
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.mismatched.is_empty()
+    }
+}
+
+pub fn compare(wanted: &[(String, String)], installed: &BTreeMap<String, String>) -> Report {
+    let mut report = Report::default();
+    for (name, version) in wanted {
+        match installed.get(name) {
+            None => report.missing.push((name.clone(), version.clone())),
+            Some(installed) if !version_matches(version, installed) => {
+                report
+                    .mismatched
+                    .push((name.clone(), installed.clone(), version.clone()));
+            }
+            Some(_) => {}
+        }
+    }
+    for (name, version) in installed {
+        if !wanted.iter().any(|(x, _)| x == name) {
+            report.extra.push((name.clone(), version.clone()));
+        }
+    }
+    report
+}
+
+pub fn print(report: &Report, lang: Lang) {
+    for (name, wanted) in &report.missing {
+        println!(
+            "{}: {} {wanted}",
+            tr(lang, "check-missing").red(),
+            name.bold()
+        );
+    }
+    for (name, installed, wanted) in &report.mismatched {
+        println!(
+            "{}: {} {installed} ({} {wanted})",
+            tr(lang, "check-mismatched").yellow(),
+            name.bold(),
+            tr(lang, "check-wanted")
+        );
+    }
+    for (name, installed) in &report.extra {
+        println!(
+            "{}: {} {installed}",
+            tr(lang, "check-extra").blue(),
+            name.bold()
+        );
+    }
+    if report.is_ok() {
+        eprintln!("{}", tr(lang, "check-ok").green());
+    }
+}
+
+mod test {
+    #[test]
+    fn compare() {
+        let pair = |x: &str, y: &str| (x.to_string(), y.to_string());
+        let wanted = vec![
+            pair("ripgrep", "13"),
+            pair("cargo-edit", "0.12.0"),
+            pair("just", "*"),
+            pair("jless", "0.9"),
+        ];
+        let installed = [
+            pair("ripgrep", "13.0.0"),
+            pair("just", "1.2.3"),
+            pair("jless", "0.10.0"),
+            pair("bat", "0.23.0"),
+        ]
+        .into_iter()
+        .collect();
+
+        let report = super::compare(&wanted, &installed);
+        assert_eq!(
+            report,
+            super::Report {
+                missing: vec![pair("cargo-edit", "0.12.0")],
+                mismatched: vec![(
+                    String::from("jless"),
+                    String::from("0.10.0"),
+                    String::from("0.9")
+                )],
+                extra: vec![pair("bat", "0.23.0")],
+            }
+        );
+        assert!(!report.is_ok());
+        assert!(!super::version_matches("1.1", "1.10.0"));
+    }
+}
@@ -0,0 +1,21 @@
+//A page listing installed tools, in markdown or HTML.
+
+use super::Context;
+use crate::paths;
+
+pub fn run(cx: &Context) -> i32 {
+    let installed: Vec<_> = cx
+        .pkgs
+        .iter()
+        .map(|x| paths::bin_name(x))
+        .filter_map(|x| Some((x, cx.map.get(x)?)))
+        .collect();
+    let entries = crate::cheatsheet::entries(&installed);
+    let (_, unknown) = crate::installed_crates(&cx.pkgs, &cx.map);
+    if cx.options.html {
+        print!("{}", crate::cheatsheet::html(&entries, &unknown));
+    } else {
+        print!("{}", crate::cheatsheet::markdown(&entries, &unknown));
+    }
+    0
+}
@@ -0,0 +1,55 @@
+//Comparing installed crates with a toolbox manifest, from a file or an HTTPS URL.
+
+use super::Context;
+use crate::{checksum, i18n::tr, network, EXIT_ERROR, EXIT_NETWORK, EXIT_POLICY_VIOLATION};
+use std::fs;
+
+pub fn run(cx: &Context) -> i32 {
+    let lang = cx.lang;
+    let Some(path) = &cx.options.against else {
+        eprintln!("{}", tr(lang, "err-check-no-manifest"));
+        return EXIT_ERROR;
+    };
+    let content = if path.starts_with("https://") {
+        match network::get(path) {
+            Ok(x) => x,
+            Err(e) => {
+                eprintln!("{} {path}: {e}", tr(lang, "err-fetch"));
+                return EXIT_NETWORK;
+            }
+        }
+    } else if path.starts_with("http://") {
+        eprintln!("{}: {path}", tr(lang, "err-insecure-url"));
+        return EXIT_ERROR;
+    } else {
+        match fs::read(path) {
+            Ok(x) => x,
+            Err(e) => {
+                eprintln!("{} {path}: {e}", tr(lang, "err-read"));
+                return EXIT_ERROR;
+            }
+        }
+    };
+    //Pinning the checksum makes sure a hosted manifest is the one that was reviewed.
+    if let Some(expected) = &cx.options.sha256 {
+        let actual = checksum::sha256(&content);
+        if !actual.eq_ignore_ascii_case(expected) {
+            eprintln!(
+                "{}: {path}\n\texpected: {expected}\n\tactual: {actual}",
+                tr(lang, "err-checksum")
+            );
+            return EXIT_POLICY_VIOLATION;
+        }
+    }
+    let wanted = crate::config::parse_pairs(&String::from_utf8_lossy(&content));
+    //Crates are compared by crate name, binaries without metadata by their own name.
+    let (mut installed, unknown) = crate::installed_crates(&cx.pkgs, &cx.map);
+    installed.extend(unknown.into_iter().map(|x| (x, String::from("n/a"))));
+    let report = crate::check::compare(&wanted, &installed);
+    crate::check::print(&report, lang);
+    if report.is_ok() {
+        0
+    } else {
+        EXIT_POLICY_VIOLATION
+    }
+}
@@ -0,0 +1,32 @@
+//Dependency versions locked in the Cargo.lock of installed crates.
+
+use super::Context;
+use crate::{i18n::tr, EXIT_ERROR};
+
+//The locked dependencies of a crate or binary, exiting if it isn't installed or has no lock file.
+fn locked(cx: &Context, name: &str) -> Vec<(String, String)> {
+    let Some(info) = cx.map.get(name) else {
+        crate::exit_not_installed(name, cx.map.keys().map(String::as_str), cx.lang);
+    };
+    let Some(deps) = crate::deps::locked(info) else {
+        eprintln!(
+            "{}: {} {}",
+            tr(cx.lang, "err-no-lock"),
+            info.name,
+            info.version
+        );
+        std::process::exit(EXIT_ERROR);
+    };
+    deps
+}
+
+pub fn run(cx: &Context, name: &str) -> i32 {
+    crate::deps::print(&locked(cx, name));
+    0
+}
+
+pub fn run_diff(cx: &Context, a: &str, b: &str) -> i32 {
+    let (a, b) = (locked(cx, a), locked(cx, b));
+    crate::deps::print_diff(&crate::deps::diff(&a, &b));
+    0
+}
@@ -0,0 +1,128 @@
+//Everything that affects how packages are found, to paste into bug reports.
+
+use crate::{
+    aliases, config_dir, crash, crates_toml, determine_pkgs_install_dir, get_pkgs_info, groups,
+    install_dir_candidates, list_pkgs, locked, paths, progress::Progress, stars, toolchains,
+    warnings::Warnings,
+};
+use std::{env, fs, io, path::Path};
+
+//Create and remove a file, the only way to tell about read-only mounts and ACLs as well as
+//permissions.
+fn check_writable(dir: &Path) -> io::Result<()> {
+    let probe = dir.join(format!(
+        ".cargo-ls-crates-write-test-{}",
+        std::process::id()
+    ));
+    fs::File::create(&probe)?;
+    fs::remove_file(&probe)
+}
+
+pub fn run() -> i32 {
+    println!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+    println!("os: {} {}", env::consts::OS, env::consts::ARCH);
+
+    println!("environment:");
+    for var in ["CARGO_INSTALL_ROOT", "CARGO_HOME", "HOME"] {
+        let value = env::var(var).unwrap_or_else(|_| String::from("<unset>"));
+        println!("\t{var}={value}");
+    }
+
+    //Show every candidate root, not only the ones that were picked, so it's clear why one was skipped.
+    println!("install roots (in order of precedence):");
+    let mut warnings = Warnings::default();
+    let roots = determine_pkgs_install_dir(&mut warnings);
+    for (variable, target, dir) in install_dir_candidates() {
+        let source = match target {
+            Some(target) => format!("{variable}, target {target}"),
+            None => variable.to_string(),
+        };
+        let Some(dir) = dir else {
+            println!("\t{source}: not set");
+            continue;
+        };
+        if roots.iter().any(|x| paths::same(x, &dir)) {
+            println!("\t{source}: {}", dir.display());
+        } else {
+            println!("\t{source}: {} (not readable, skipped)", dir.display());
+        }
+    }
+
+    //The files the tool reads or writes, most only exist once something was set.
+    match config_dir() {
+        Some(dir) => println!("config directory: {}", dir.display()),
+        None => println!("config directory: not found"),
+    }
+    println!("config files:");
+    let files = [
+        ("starred crates", stars::path()),
+        ("aliases", aliases::path()),
+        ("categories", groups::path()),
+        ("cargo versions", toolchains::path()),
+        ("crates updated without --locked", locked::path()),
+        ("crash log", crash::log_path()),
+    ];
+    for (name, path) in files {
+        match path {
+            Some(path) => {
+                let state = if path.is_file() { "exists" } else { "missing" };
+                println!("\t{name}: {} ({state})", path.display());
+            }
+            None => println!("\t{name}: none (config directory not found)"),
+        }
+    }
+    println!("cache: none");
+
+    //Installs fail with confusing errors when cargo can't write to the bin directory.
+    println!("bin directories:");
+    for dir in &roots {
+        let bin = dir.join("bin");
+        match check_writable(&bin) {
+            Ok(()) => println!("\t{}: writable", bin.display()),
+            //cargo creates it on the first install.
+            Err(_) if !bin.exists() => match check_writable(dir) {
+                Ok(()) => println!("\t{}: missing, created on install", bin.display()),
+                Err(e) => println!("\t{}: missing, can't be created ({e})", bin.display()),
+            },
+            Err(e) => println!("\t{}: not writable ({e})", bin.display()),
+        }
+    }
+
+    println!("counts:");
+    for dir in roots {
+        let bins = list_pkgs(&dir, false, &mut warnings).map_or(0, |x| x.len());
+        let recorded = crates_toml::load(&dir, &mut warnings).len();
+        let infos =
+            get_pkgs_info(&dir, &mut Progress::hidden(), &mut warnings).map_or(0, |x| x.len());
+        println!(
+            "\t{}: {bins} binaries, {recorded} recorded installs, {infos} packages with metadata",
+            dir.display()
+        );
+    }
+
+    if warnings.not_programs().is_empty() {
+        println!("skipped files in bin: none");
+    } else {
+        println!("skipped files in bin (empty or not executable):");
+        for path in warnings.not_programs() {
+            println!("\t{}", path.display());
+        }
+    }
+    if warnings.registries().is_empty() {
+        println!("unreadable registries: none");
+    } else {
+        println!("unreadable registries:");
+        for (path, error) in warnings.registries() {
+            println!("\t{}: {error}", path.display());
+        }
+    }
+    if warnings.errors().is_empty() {
+        println!("unreadable paths: none");
+    } else {
+        println!("unreadable paths:");
+        for (path, error) in warnings.errors() {
+            println!("\t{}: {error}", path.display());
+        }
+    }
+    0
+}
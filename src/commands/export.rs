@@ -0,0 +1,22 @@
+//Recipes installing the same crate versions somewhere else.
+
+use super::Context;
+use crate::{export::Install, i18n::tr, EXIT_ERROR};
+
+pub fn run(cx: &Context) -> i32 {
+    let (crates, unknown) = crate::installed_crates(&cx.pkgs, &cx.map);
+    //Sorted by crate name, the map has every crate by its name too.
+    let installs: Vec<Install> = crates
+        .keys()
+        .filter_map(|x| Some(Install::new(cx.map.get(x)?)))
+        .collect();
+    if cx.options.dockerfile {
+        print!("{}", crate::export::dockerfile(&installs, &unknown));
+    } else if cx.options.devcontainer {
+        print!("{}", crate::export::devcontainer(&installs, &unknown));
+    } else {
+        eprintln!("{}", tr(cx.lang, "err-export-format"));
+        return EXIT_ERROR;
+    }
+    0
+}
@@ -0,0 +1,13 @@
+//Crates grouped by category, with overlapping tools highlighted.
+
+use super::Context;
+use crate::paths;
+
+pub fn run(cx: &Context) -> i32 {
+    let infos = cx
+        .pkgs
+        .iter()
+        .filter_map(|x| cx.map.get(paths::bin_name(x)));
+    crate::groups::print(&crate::groups::group(infos), cx.lang, cx.style);
+    0
+}
@@ -0,0 +1,118 @@
+//Everything known about one installed crate.
+
+use super::Context;
+use crate::{build_info, dates, i18n::tr, locale::Style, paths, toolchains};
+use colored::Colorize;
+use std::{fs, time::SystemTime};
+
+//File size in binary units, like 4.2 MiB, written the way the locale writes numbers.
+fn size(bytes: u64, style: Style) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", style.count(bytes as usize));
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{} {}", style.decimal(value), UNITS[unit])
+}
+
+pub fn run(cx: &Context, name: &str) -> i32 {
+    let (lang, style, pkgs, map) = (cx.lang, cx.style, &cx.pkgs, &cx.map);
+    //The crate name works as well as the binary name, and selects every binary of the crate.
+    let crate_name = map.get(name).map_or(name, |x| x.name.as_str());
+    let bins: Vec<(&str, _)> = pkgs
+        .iter()
+        .filter(|x| {
+            let bin = paths::bin_name(x);
+            bin == name || map.get(bin).is_some_and(|x| x.name == crate_name)
+        })
+        .filter_map(|x| Some((paths::bin_name(x), crate::bin_path(&cx.install_dirs, x)?)))
+        .collect();
+    if bins.is_empty() {
+        let names = pkgs.iter().map(|x| paths::bin_name(x));
+        let crates = names
+            .clone()
+            .filter_map(|x| Some(map.get(x)?.name.as_str()));
+        crate::exit_not_installed(name, names.chain(crates), lang);
+    }
+    let info = map.get(bins[0].0);
+    let or_na = |x: Option<String>| {
+        x.filter(|x| !x.is_empty())
+            .unwrap_or_else(|| String::from("n/a"))
+    };
+    let line = |label: &'static str, value: &str| println!("{}: {value}", tr(lang, label).bold());
+
+    line(
+        "label-name",
+        &info
+            .map_or(bins[0].0, |x| &x.name)
+            .green()
+            .bold()
+            .to_string(),
+    );
+    line("label-version", &or_na(info.map(|x| x.version.clone())));
+    line(
+        "label-description",
+        &or_na(info.map(|x| x.description.clone())),
+    );
+    line("label-source", &or_na(info.map(|x| x.source.clone())));
+    line(
+        "label-features",
+        &or_na(info.map(|x| x.features.join(", "))),
+    );
+    //A crate can be installed in more than one root.
+    let mut roots: Vec<String> = bins
+        .iter()
+        .filter_map(|(_, path)| Some(path.parent()?.parent()?.display().to_string()))
+        .collect();
+    roots.sort();
+    roots.dedup();
+    line("label-root", &roots.join(", "));
+    println!("{}:", tr(lang, "label-binaries").bold());
+    for (bin, path) in &bins {
+        let bytes = fs::metadata(path).map_or(0, |x| x.len());
+        println!(
+            "\t{} {} ({})",
+            bin.green(),
+            path.display(),
+            size(bytes, style).dimmed()
+        );
+    }
+    //Cargo writes the binaries last, so the newest one is when it was installed.
+    let build = build_info::read(&bins[0].1);
+    let installed = bins
+        .iter()
+        .filter_map(|(_, path)| fs::metadata(path).and_then(|x| x.modified()).ok())
+        .max();
+    let relative_time = super::relative_time(&cx.options);
+    let installed = installed.map(|x| {
+        let ago = relative_time.then(|| dates::ago(lang, x, SystemTime::now()));
+        ago.flatten().unwrap_or_else(|| style.date(x))
+    });
+    line("label-installed", &or_na(installed));
+    line(
+        "label-compiler",
+        &or_na(build.rustc.map(|x| format!("rustc {x}"))),
+    );
+    if let Some(version) = toolchains::load().get(crate_name) {
+        line("label-installed-with", version);
+    }
+    0
+}
+
+mod test {
+    #[test]
+    fn size() {
+        use crate::locale::Style;
+        assert_eq!(super::size(0, Style::Iso), "0 B");
+        assert_eq!(super::size(1023, Style::Us), "1,023 B");
+        assert_eq!(super::size(1536, Style::Iso), "1.5 KiB");
+        assert_eq!(super::size(4404019, Style::Iso), "4.2 MiB");
+        assert_eq!(super::size(4404019, Style::Pl), "4,2 MiB");
+        assert_eq!(super::size(3 << 30, Style::En), "3.0 GiB");
+    }
+}
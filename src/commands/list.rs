@@ -0,0 +1,289 @@
+//The listing of installed crates, what runs without a subcommand.
+
+use super::Context;
+use crate::{
+    bin_path, build_info, dates, groups, i18n::tr, package, paths, system, term, toolchains,
+};
+use colored::Colorize;
+use std::{collections::HashMap, time::SystemTime};
+
+pub fn run(cx: &mut Context) -> i32 {
+    let (lang, style) = (cx.lang, cx.style);
+    let (print_versions, print_descs, _) = cx.options.unpack();
+    let Context {
+        options,
+        install_dirs,
+        pkgs,
+        map,
+        stars,
+        timings,
+        warnings,
+        ..
+    } = cx;
+
+    //Versions installed with the system package manager instead of the listing.
+    if options.compare_system {
+        let installed: Vec<_> = pkgs
+            .iter()
+            .map(|x| paths::bin_name(x))
+            .filter_map(|x| Some((x, map.get(x)?)))
+            .collect();
+        system::print(&system::compare(&installed, &system::installed()), lang);
+        return 0;
+    }
+
+    if options.json {
+        let installed = pkgs.iter().map(|x| {
+            let root = install_dirs
+                .iter()
+                .find(|dir| dir.join("bin").join(x).is_file());
+            let bin = paths::bin_name(x);
+            (bin, map.get(bin), root.cloned().unwrap_or_default())
+        });
+        let packages = package::packages(installed);
+        print!("{}", package::to_json(&packages, &warnings.to_json()));
+        return 0;
+    }
+
+    //The listing is sorted by binary name already, binaries without metadata keep their own.
+    if options.crate_names {
+        pkgs.sort_by_key(|x| {
+            let bin = paths::bin_name(x);
+            map.get(bin).map_or(bin, |x| x.name.as_str()).to_string()
+        });
+    }
+
+    //Move crates under their headings, keeping the order within each.
+    let headings: HashMap<String, String> = if options.by_category {
+        let custom = groups::load_headings();
+        pkgs.iter()
+            .map(|x| {
+                let bin = paths::bin_name(x);
+                (x.clone(), groups::heading(bin, map.get(bin), &custom, lang))
+            })
+            .collect()
+    } else {
+        HashMap::new()
+    };
+    let other = tr(lang, groups::OTHER);
+    pkgs.sort_by_key(|x| headings.get(x).map(|x| (x == other, x.clone())));
+
+    //Without any details every package goes on one line.
+    let one_line = !(print_descs
+        || print_versions
+        || options.all_source_versions
+        || options.docs
+        || options.wide
+        || options.build_info
+        || options.plain);
+    let width = term::width(options.width);
+    let relative_time = super::relative_time(options);
+    let now = SystemTime::now();
+    //Cargo versions recorded by update, crates installed some other way have none.
+    let cargo_versions = if options.build_info {
+        toolchains::load()
+    } else {
+        HashMap::new()
+    };
+
+    //Print info out
+    let mut current_heading = None;
+    //Characters printed on the current line in one-line mode.
+    let mut column = 0;
+    for file_name in pkgs.iter() {
+        if let Some(heading) = headings.get(file_name) {
+            if current_heading != Some(heading) {
+                if one_line && current_heading.is_some() {
+                    println!();
+                }
+                column = 0;
+                println!("{}", format!("{heading}:").bold().underline());
+                current_heading = Some(heading);
+            }
+        }
+        let pkg = paths::bin_name(file_name);
+        //The name the listing uses, and the other one when they differ, like rg from ripgrep.
+        let crate_name = map.get(pkg).map_or(pkg, |x| x.name.as_str());
+        let (name, other_name) = if options.crate_names {
+            (crate_name, (crate_name != pkg).then_some(pkg))
+        } else {
+            (pkg, (crate_name != pkg).then_some(crate_name))
+        };
+        //Get package description
+        let (mut ver, mut desc) = map
+            .get(pkg)
+            .map(|x| (x.version.clone(), x.description.clone()))
+            .unwrap_or_else(|| (String::from("n/a"), String::from("n/a")));
+        let docs = map
+            .get(pkg)
+            .map(|x| x.documentation.clone())
+            .filter(|x| !x.is_empty())
+            .unwrap_or_else(|| String::from("n/a"));
+        //Homepage and repository, from the manifest like everything else, so no network is needed.
+        let links = [
+            ("label-homepage", map.get(pkg).map(|x| x.homepage.clone())),
+            (
+                "label-repository",
+                map.get(pkg).map(|x| x.repository.clone()),
+            ),
+        ]
+        .map(|(label, url)| {
+            let url = url.filter(|x| !x.is_empty());
+            (label, url.unwrap_or_else(|| String::from("n/a")))
+        });
+        let other_versions = map
+            .get(pkg)
+            .map(|x| {
+                let others = x.source_versions.iter().filter(|v| **v != x.version);
+                others.cloned().collect::<Vec<_>>().join(", ")
+            })
+            .unwrap_or_default();
+        //Get the compiler version and build date
+        let (rustc, built) = if options.build_info {
+            let info = timings.time("build info", || {
+                bin_path(install_dirs, file_name)
+                    .map(|x| build_info::read(&x))
+                    .unwrap_or_default()
+            });
+            (
+                info.rustc
+                    .map_or_else(|| String::from("n/a"), |x| format!("rustc {x}")),
+                info.built.map_or_else(
+                    || String::from("n/a"),
+                    |x| {
+                        let ago = relative_time.then(|| dates::ago(lang, x, now));
+                        ago.flatten().unwrap_or_else(|| style.date(x))
+                    },
+                ),
+            )
+        } else {
+            (String::new(), String::new())
+        };
+        let cargo_version = cargo_versions.get(crate_name);
+        let starred = stars.iter().any(|x| x == pkg);
+
+        //Plain mode prints one "label: value" line per field and an empty line between packages.
+        if options.plain {
+            println!("{}: {name}", tr(lang, "label-name"));
+            if let Some(other_name) = other_name {
+                let label = if options.crate_names {
+                    "label-binary"
+                } else {
+                    "label-crate"
+                };
+                println!("{}: {other_name}", tr(lang, label));
+            }
+            if starred {
+                println!("{}: {}", tr(lang, "label-starred"), tr(lang, "yes"));
+            }
+            if print_versions || options.all_source_versions {
+                println!("{}: {ver}", tr(lang, "label-version"));
+            }
+            if print_descs {
+                println!("{}: {desc}", tr(lang, "label-description"));
+            }
+            if options.all_source_versions && !other_versions.is_empty() {
+                println!("{}: {other_versions}", tr(lang, "label-other-versions"));
+            }
+            if options.docs {
+                println!("{}: {docs}", tr(lang, "label-documentation"));
+            }
+            if options.wide {
+                for (label, url) in &links {
+                    println!("{}: {url}", tr(lang, label));
+                }
+            }
+            if options.build_info {
+                println!("{}: {rustc}", tr(lang, "label-compiler"));
+                println!("{}: {built}", tr(lang, "label-built"));
+                if let Some(version) = cargo_version {
+                    println!("{}: {version}", tr(lang, "label-installed-with"));
+                }
+            }
+            println!();
+            continue;
+        }
+
+        //If user passed -v print version info, additionally if -d is passed print package
+        //descriptions.
+        if print_versions || options.all_source_versions {
+            ver = format!(" {}", ver.yellow());
+        } else {
+            ver = String::new();
+        }
+        if options.all_source_versions && !other_versions.is_empty() {
+            ver += &format!(
+                " ({}: {})",
+                tr(lang, "label-other-versions"),
+                other_versions
+            )
+            .dimmed()
+            .to_string();
+        }
+        let docs = if options.docs {
+            format!(" {}", docs.underline())
+        } else {
+            String::new()
+        };
+        let build = if options.build_info {
+            let cargo = cargo_version
+                .map(|x| {
+                    format!(" ({}: {x})", tr(lang, "label-installed-with"))
+                        .dimmed()
+                        .to_string()
+                })
+                .unwrap_or_default();
+            format!(" {} {}{cargo}", rustc.magenta(), built.cyan())
+        } else {
+            String::new()
+        };
+        let star = if starred {
+            format!("{} ", "★".yellow())
+        } else {
+            String::new()
+        };
+        let other_name = other_name
+            .map(|x| format!(" ({x})").dimmed().to_string())
+            .unwrap_or_default();
+        let fields = [&star, name, &other_name, &ver, &docs, &build];
+        let used: usize = fields.iter().map(|x| term::visible_len(x)).sum();
+        //The description is the only field that gets shortened to fit, the rest are short or
+        //need to be copied whole.
+        if print_descs {
+            if let Some(width) = width {
+                //Leave room for the spaces before and after it.
+                desc = term::truncate(&desc, width.saturating_sub(used + 2));
+            }
+            desc = format!(" {}", desc.blue());
+        } else {
+            desc = String::new();
+        }
+        let entry = format!(
+            "{star}{}{other_name}{ver}{desc}{docs}{build} ",
+            name.green().bold()
+        );
+        if one_line {
+            let len = term::visible_len(&entry);
+            if width.is_some_and(|x| column > 0 && column + len > x) {
+                println!();
+                column = 0;
+            }
+            column += len;
+            print!("{entry}");
+        } else {
+            println!("{entry}");
+        }
+        //Beneath the entry, URLs are too long to fit beside it.
+        if options.wide {
+            for (label, url) in &links {
+                println!("\t{}: {}", tr(lang, label).dimmed(), url.underline());
+            }
+        }
+    }
+
+    //Nothing is printed when every package was filtered out.
+    if one_line && column > 0 {
+        println!();
+    }
+    0
+}
@@ -0,0 +1,26 @@
+//The man page of a crate's binary, or its --help output.
+
+use super::Context;
+use crate::{i18n::tr, paths, EXIT_ERROR};
+
+pub fn run(cx: &Context, name: &str) -> i32 {
+    let (pkgs, map) = (&cx.pkgs, &cx.map);
+    //The crate name works as well as the binary name.
+    let found = pkgs.iter().find(|x| {
+        let bin = paths::bin_name(x);
+        bin == name || map.get(bin).is_some_and(|x| x.name == *name)
+    });
+    let Some((file_name, path)) =
+        found.and_then(|x| Some((x, crate::bin_path(&cx.install_dirs, x)?)))
+    else {
+        let bins = pkgs.iter().map(|x| paths::bin_name(x));
+        let crates = bins.clone().filter_map(|x| Some(map.get(x)?.name.as_str()));
+        crate::exit_not_installed(name, bins.chain(crates), cx.lang);
+    };
+    let bin = paths::bin_name(file_name);
+    if let Err(e) = crate::man::show(bin, &path, map.get(bin)) {
+        eprintln!("{} {}: {e}", tr(cx.lang, "err-run"), path.display());
+        return EXIT_ERROR;
+    }
+    0
+}
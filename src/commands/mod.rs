@@ -0,0 +1,126 @@
+//Subcommands, one module each. main finds the install roots and what's installed in them, and the
+//command given on the command line does the rest and says what to exit with.
+
+mod cheatsheet;
+mod check;
+mod deps;
+pub mod doctor;
+mod export;
+mod groups;
+mod info;
+mod list;
+mod man;
+mod outdated;
+mod star;
+mod uninstall;
+mod update;
+
+use crate::{i18n::Lang, locale::Style, warnings::Warnings, CliOptions, PkgInfo, Timings};
+use std::{
+    collections::HashMap,
+    io::{self, IsTerminal},
+    path::PathBuf,
+};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Command {
+    //List installed crates, what runs without a subcommand.
+    #[default]
+    List,
+    //Print the environment, to paste into bug reports. Also called debug-info.
+    Doctor,
+    Check,
+    Cheatsheet,
+    //Crate to list the locked dependencies of.
+    Deps(String),
+    //Two crates to compare the locked dependencies of.
+    DepsDiff(String, String),
+    Export,
+    Groups,
+    //Crate or binary to show everything known about.
+    Info(String),
+    //Crate or binary to show the manual of.
+    Man(String),
+    Star(String),
+    Unstar(String),
+    //Look up newer versions on crates.io.
+    Outdated,
+    //Crates or binaries to reinstall if they're outdated, all of them when empty.
+    Update(Vec<String>),
+    //Crates or binaries to uninstall.
+    Uninstall(Vec<String>),
+}
+
+impl Command {
+    //The command a word on the command line starts, None if it isn't one. Arguments the command
+    //takes are filled in by the parser.
+    pub fn from_name(name: &str) -> Option<Command> {
+        Some(match name {
+            "list" => Command::List,
+            "doctor" | "debug-info" => Command::Doctor,
+            "check" => Command::Check,
+            "cheatsheet" => Command::Cheatsheet,
+            "deps" => Command::Deps(String::new()),
+            "export" => Command::Export,
+            "groups" => Command::Groups,
+            "info" => Command::Info(String::new()),
+            "man" => Command::Man(String::new()),
+            "star" => Command::Star(String::new()),
+            "unstar" => Command::Unstar(String::new()),
+            "outdated" => Command::Outdated,
+            "update" => Command::Update(Vec::new()),
+            "uninstall" => Command::Uninstall(Vec::new()),
+            _ => return None,
+        })
+    }
+}
+
+//What's installed, for the command to work with.
+pub struct Context {
+    pub options: CliOptions,
+    pub lang: Lang,
+    pub style: Style,
+    pub install_dirs: Vec<PathBuf>,
+    //File names in the bin directories, sorted.
+    pub pkgs: Vec<String>,
+    //Metadata by binary name and by crate name.
+    pub map: HashMap<String, PkgInfo>,
+    //Starred binaries.
+    pub stars: Vec<String>,
+    pub timings: Timings,
+    //What couldn't be read while scanning, already printed unless the listing is JSON.
+    pub warnings: Warnings,
+}
+
+//Times like "3 days ago" are easier to read, but dates are stable for scripts and logs, so they're
+//only written that way in a terminal.
+pub fn relative_time(options: &CliOptions) -> bool {
+    !(options.absolute_time || options.iso) && io::stdout().is_terminal()
+}
+
+//Run the command given on the command line, returning the exit code. Timings are reported once
+//it's done.
+pub fn run(cx: &mut Context) -> i32 {
+    let code = match cx.options.command.clone() {
+        Command::List => list::run(cx),
+        //It runs before anything is scanned, see main.
+        Command::Doctor => doctor::run(),
+        Command::Check => check::run(cx),
+        Command::Cheatsheet => cheatsheet::run(cx),
+        Command::Deps(name) => deps::run(cx, &name),
+        Command::DepsDiff(a, b) => deps::run_diff(cx, &a, &b),
+        Command::Export => export::run(cx),
+        Command::Groups => groups::run(cx),
+        Command::Info(name) => info::run(cx, &name),
+        Command::Man(name) => man::run(cx, &name),
+        Command::Star(name) => star::run(cx, &name, true),
+        Command::Unstar(name) => star::run(cx, &name, false),
+        Command::Outdated => outdated::run(cx),
+        Command::Update(names) => update::run(cx, &names),
+        Command::Uninstall(names) => uninstall::run(cx, &names),
+    };
+    if cx.options.timing {
+        cx.timings.report();
+    }
+    code
+}
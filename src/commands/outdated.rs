@@ -0,0 +1,59 @@
+//Crates with a newer version on crates.io.
+
+use super::Context;
+use crate::{
+    i18n::{tr, Lang},
+    network::{self, Failed, Outdated},
+    paths,
+    progress::Progress,
+    CliOptions, PkgInfo, Timings, EXIT_NETWORK,
+};
+use std::{
+    collections::{BTreeSet, HashMap},
+    process::exit,
+};
+
+//Crates installed from crates.io, once each. Crates from git, a local path or another registry
+//can't be looked up there.
+pub fn crates_io_crates<'a>(
+    pkgs: &[String],
+    map: &'a HashMap<String, PkgInfo>,
+) -> Vec<(&'a str, &'a str)> {
+    pkgs.iter()
+        .filter_map(|x| map.get(paths::bin_name(x)))
+        .filter(|x| network::is_crates_io(&x.source))
+        .map(|x| (x.name.as_str(), x.version.as_str()))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+//Look up newer versions on crates.io, timed as the network phase, exiting if curl can't be run at
+//all. The bar counts crates looked up.
+pub fn look_up(
+    crates: &[(&str, &str)],
+    options: &CliOptions,
+    timings: &mut Timings,
+    lang: Lang,
+) -> (Vec<Outdated>, Vec<Failed>) {
+    //Plain mode is meant for screen readers, which would read every redraw.
+    let mut progress = Progress::new("crates.io", !(options.no_progress || options.plain));
+    match timings.time("network", || network::outdated(crates, &mut progress)) {
+        Ok(x) => x,
+        Err(e) => {
+            eprintln!("{} curl: {e}", tr(lang, "err-run"));
+            exit(EXIT_NETWORK);
+        }
+    }
+}
+
+pub fn run(cx: &mut Context) -> i32 {
+    let crates = crates_io_crates(&cx.pkgs, &cx.map);
+    let (outdated, failed) = look_up(&crates, &cx.options, &mut cx.timings, cx.lang);
+    network::print(&outdated, &failed, cx.lang);
+    if !failed.is_empty() && failed.len() == crates.len() {
+        EXIT_NETWORK
+    } else {
+        0
+    }
+}
@@ -0,0 +1,23 @@
+//Starring binaries, so they can be listed on their own with --starred.
+
+use super::Context;
+use crate::{i18n::tr, paths, stars, EXIT_ERROR};
+
+//Star or unstar one of the listed binaries.
+pub fn run(cx: &Context, name: &str, starred: bool) -> i32 {
+    let lang = cx.lang;
+    if !cx.pkgs.iter().any(|x| paths::bin_name(x) == name) {
+        crate::exit_not_installed(name, cx.pkgs.iter().map(|x| paths::bin_name(x)), lang);
+    }
+    //Confirmations are diagnostics like errors, stdout only gets data.
+    match stars::set(name, starred) {
+        Ok(true) if starred => eprintln!("{}: {name}", tr(lang, "msg-starred")),
+        Ok(true) => eprintln!("{}: {name}", tr(lang, "msg-unstarred")),
+        Ok(false) => eprintln!("{}: {name}", tr(lang, "msg-unchanged")),
+        Err(e) => {
+            eprintln!("{}: {e}", tr(lang, "err-stars-write"));
+            return EXIT_ERROR;
+        }
+    }
+    0
+}
@@ -0,0 +1,66 @@
+//Uninstalling crates, or removing binaries cargo has no record of.
+
+use super::Context;
+use crate::{glob, i18n::tr, paths, uninstall::Removal, EXIT_ERROR};
+
+pub fn run(cx: &Context, names: &[String]) -> i32 {
+    let lang = cx.lang;
+    if names.is_empty() {
+        eprintln!("{}", tr(lang, "err-uninstall-no-names"));
+        return EXIT_ERROR;
+    }
+    //A binary installed in several roots is listed once for each, so it's removed from all of them.
+    let mut pkgs: Vec<&String> = cx.pkgs.iter().collect();
+    pkgs.dedup();
+    let installed: Vec<_> = pkgs
+        .into_iter()
+        .flat_map(|x| {
+            let bin = paths::bin_name(x);
+            cx.install_dirs
+                .iter()
+                .map(move |dir| dir.join("bin").join(x))
+                .filter(|x| x.is_file())
+                .map(move |path| (bin, cx.map.get(bin), path))
+        })
+        .collect();
+    //Patterns match binaries and the crates cargo recorded installing.
+    let candidates: Vec<&str> = installed
+        .iter()
+        .flat_map(|(bin, info, _)| {
+            let name = info
+                .filter(|x| !x.source.is_empty())
+                .map(|x| x.name.as_str());
+            [Some(*bin), name]
+        })
+        .flatten()
+        .collect();
+    let expanded = match glob::expand(names, &candidates) {
+        Ok(x) => x,
+        Err(name) => crate::exit_not_installed(name, candidates, lang),
+    };
+    let removals = match crate::uninstall::plan(&expanded, &installed) {
+        Ok(x) => x,
+        Err(name) => {
+            crate::exit_not_installed(name, cx.pkgs.iter().map(|x| paths::bin_name(x)), lang)
+        }
+    };
+    if cx.options.dry_run {
+        for x in &removals {
+            println!("{}", x.describe());
+        }
+        return 0;
+    }
+    //A pattern can match more than was meant, and a binary cargo has no record of is deleted for
+    //good, so both are confirmed first.
+    let confirm = names.iter().any(|x| glob::is_pattern(x))
+        || removals.iter().any(|x| matches!(x, Removal::File(_)));
+    if confirm && !cx.options.yes && !crate::uninstall::confirm(&removals, lang) {
+        eprintln!("{}", tr(lang, "msg-uninstall-cancelled"));
+        return EXIT_ERROR;
+    }
+    if crate::uninstall::run(&removals, lang) {
+        0
+    } else {
+        EXIT_ERROR
+    }
+}
@@ -0,0 +1,110 @@
+//Reinstalling outdated crates, or building installed ones for another target.
+
+use super::{outdated, Context};
+use crate::{
+    glob,
+    i18n::tr,
+    locked,
+    network::{self, Outdated},
+    paths,
+    update::Update,
+    EXIT_ERROR, EXIT_NETWORK,
+};
+use colored::Colorize;
+
+pub fn run(cx: &mut Context, names: &[String]) -> i32 {
+    let (lang, pkgs, map) = (cx.lang, &cx.pkgs, &cx.map);
+    //Building everything for another target takes long and fills a new root, so it's asked for.
+    if cx.options.target.is_some() && names.is_empty() {
+        eprintln!("{}", tr(lang, "err-update-target-no-names"));
+        return EXIT_ERROR;
+    }
+    //Patterns match binaries and crates.
+    let candidates: Vec<&str> = map.keys().map(String::as_str).collect();
+    let names = match glob::expand(names, &candidates) {
+        Ok(x) => x,
+        Err(name) => crate::exit_not_installed(name, candidates, lang),
+    };
+    for name in &names {
+        if !map.contains_key(name) {
+            crate::exit_not_installed(name, map.keys().map(String::as_str), lang);
+        }
+    }
+    let mut crates = outdated::crates_io_crates(pkgs, map);
+    //Binary names select their crate.
+    if !names.is_empty() {
+        crates.retain(|(crate_name, _)| names.iter().any(|x| map[x].name == *crate_name));
+    }
+    let outdated = match &cx.options.target {
+        //Another target gets the versions installed for the host, they don't need looking up.
+        Some(_) => crates
+            .iter()
+            .map(|(name, version)| Outdated {
+                name: name.to_string(),
+                installed: version.to_string(),
+                latest: version.to_string(),
+            })
+            .collect(),
+        None => {
+            let (outdated, failed) = outdated::look_up(&crates, &cx.options, &mut cx.timings, lang);
+            network::warn(&failed, lang);
+            if outdated.is_empty() {
+                if failed.is_empty() {
+                    eprintln!("{}", tr(lang, "msg-up-to-date"));
+                    return 0;
+                }
+                return EXIT_NETWORK;
+            }
+            outdated
+        }
+    };
+    //The choice is remembered, so later updates of the same crates make it again.
+    if let Some(locked) = cx.options.locked {
+        let names: Vec<&str> = outdated.iter().map(|x| x.name.as_str()).collect();
+        if let Err(e) = locked::set(&names, locked) {
+            eprintln!(
+                "{}: {}: {e}",
+                tr(lang, "warn-label").yellow(),
+                tr(lang, "warn-locked")
+            );
+        }
+    }
+    let unlocked = locked::unlocked();
+    let updates: Vec<Update> = outdated
+        .into_iter()
+        .map(|x| {
+            let bin = pkgs.iter().find(|bin| {
+                map.get(paths::bin_name(bin))
+                    .is_some_and(|y| y.name == x.name)
+            });
+            let root = bin
+                .and_then(|bin| crate::bin_path(&cx.install_dirs, bin))
+                .and_then(|path| Some(path.parent()?.parent()?.to_path_buf()));
+            let target = cx.options.target.clone();
+            Update {
+                features: map[&x.name].features.clone(),
+                no_default_features: map[&x.name].no_default_features,
+                all_features: map[&x.name].all_features,
+                //Binaries for another target go to a root of their own, named after it, so they
+                //don't replace the ones the host runs.
+                root: match &target {
+                    Some(target) => root.map(|x| x.join("targets").join(target)),
+                    None => root,
+                },
+                target,
+                locked: cx
+                    .options
+                    .locked
+                    .unwrap_or_else(|| !unlocked.contains(&x.name)),
+                outdated: x,
+            }
+        })
+        .collect();
+    let results = crate::update::run(&updates, lang);
+    crate::update::print_summary(&updates, &results, lang);
+    if results.iter().all(Result::is_ok) {
+        0
+    } else {
+        EXIT_ERROR
+    }
+}
@@ -0,0 +1,33 @@
+//The key = value files cargo-ls-crates reads its settings from: aliases, groups, toolchains and
+//toolbox manifests. Section headers and comments are skipped and quotes around either side are
+//dropped, which is as much TOML as they need.
+
+pub fn parse_pairs(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .map(|x| x.split('#').next().unwrap_or_default().trim())
+        .filter(|x| !x.is_empty() && !x.starts_with('['))
+        .filter_map(|x| x.split_once('='))
+        .map(|(key, value)| {
+            (
+                key.trim().trim_matches('"').to_string(),
+                value.trim().trim_matches('"').to_string(),
+            )
+        })
+        .collect()
+}
+
+mod test {
+    #[test]
+    fn parse_pairs() {
+        let manifest = "# team toolbox\n[tools]\nripgrep = \"13\"\n\"cargo-edit\" = \"0.12.0\" # pinned\n\njust = \"*\"\n";
+        assert_eq!(
+            super::parse_pairs(manifest),
+            vec![
+                (String::from("ripgrep"), String::from("13")),
+                (String::from("cargo-edit"), String::from("0.12.0")),
+                (String::from("just"), String::from("*")),
+            ]
+        );
+    }
+}
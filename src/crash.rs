@@ -0,0 +1,77 @@
+//Replace the raw panic message with a short report of what's needed to reproduce the crash, and
+//keep the backtrace in a log file rather than on the screen.
+
+use crate::{
+    i18n::{tr, Lang},
+    warnings::Warnings,
+};
+use std::{backtrace::Backtrace, env, fs, panic, path::PathBuf};
+
+//In the tool's own directory rather than the shared temp directory, where another user could put a
+//symlink in its place.
+pub fn log_path() -> Option<PathBuf> {
+    Some(crate::config_dir()?.join("crash.log"))
+}
+
+pub fn report(message: &str, args: &[String], roots: &[PathBuf], lang: Lang) -> String {
+    let roots: Vec<String> = roots.iter().map(|x| x.display().to_string()).collect();
+    format!(
+        "{}\n\tmessage: {message}\n\tversion: {} {}\n\tos: {} {}\n\targuments: {}\n\troots: {}\n{}\n",
+        tr(lang, "err-crash"),
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        env::consts::OS,
+        env::consts::ARCH,
+        args.join(" "),
+        if roots.is_empty() {
+            String::from("none")
+        } else {
+            roots.join(", ")
+        },
+        tr(lang, "msg-crash-report")
+    )
+}
+
+pub fn install(args: Vec<String>, lang: Lang) {
+    panic::set_hook(Box::new(move |info| {
+        let payload = info.payload();
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown");
+        let message = match info.location() {
+            Some(x) => format!("{message} ({}:{})", x.file(), x.line()),
+            None => message.to_string(),
+        };
+        let roots = crate::determine_pkgs_install_dir(&mut Warnings::default());
+        let report = report(&message, &args, &roots, lang);
+        eprint!("{report}");
+        let Some(log) = log_path() else {
+            return;
+        };
+        let backtrace = Backtrace::force_capture();
+        let written = log
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&log, format!("{report}\n{backtrace}\n")));
+        if written.is_ok() {
+            eprintln!("{}: {}", tr(lang, "msg-crash-log"), log.display());
+        }
+    }));
+}
+
+mod test {
+    #[test]
+    fn report() {
+        let report = super::report(
+            "index out of bounds (src/main.rs:1)",
+            &[String::from("ls-crates"), String::from("-v")],
+            &[std::path::PathBuf::from("/home/user/.cargo")],
+            crate::i18n::Lang::En,
+        );
+        assert!(report.starts_with("cargo-ls-crates crashed. This is a bug.\n\tmessage: index out of bounds (src/main.rs:1)\n\tversion: "));
+        assert!(report.contains("\n\targuments: ls-crates -v\n\troots: /home/user/.cargo\n"));
+        assert!(report.ends_with("cargo ls-crates doctor` to a new issue at https://github.com/bejker123/cargo-crates/issues\n"));
+    }
+}
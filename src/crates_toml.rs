@@ -0,0 +1,180 @@
+//Cargo's record of installed crates, .crates.toml in the install root:
+//
+//  [v1]
+//  "ripgrep 13.0.0 (registry+https://github.com/rust-lang/crates.io-index)" = ["rg"]
+//
+//and .crates2.json next to it, which newer versions of cargo also write, with the same keys and the
+//features each crate was built with.
+//
+//Unlike registry/src, which keeps the sources of every version ever built and of dependencies that
+//were never installed, it has exactly the installed version of each crate.
+
+use crate::{json, warnings::Warnings};
+use std::{fs, path::Path};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Installed {
+    pub name: String,
+    pub version: String,
+    //Where the crate was installed from, like `registry+https://...` or `git+https://...#rev`.
+    pub source: String,
+    pub bins: Vec<String>,
+    //Features enabled on top of the default ones, only recorded in .crates2.json like the flags
+    //below.
+    pub features: Vec<String>,
+    //Installed with --no-default-features.
+    pub no_default_features: bool,
+    //Installed with --all-features.
+    pub all_features: bool,
+}
+
+//Not installed from a registry, so it can't be reinstalled by name and version.
+pub fn is_local(source: &str) -> bool {
+    source.starts_with("git+") || source.starts_with("path+")
+}
+
+//Split a key like `ripgrep 13.0.0 (registry+https://...)` into name, version and source.
+fn parse_key(key: &str) -> Option<(String, String, String)> {
+    let mut fields = key.splitn(3, ' ');
+    let (name, version) = (fields.next()?, fields.next()?);
+    let source = fields.next().unwrap_or_default();
+    Some((
+        name.to_string(),
+        version.to_string(),
+        source.trim_matches(['(', ')']).to_string(),
+    ))
+}
+
+pub fn parse(content: &str) -> Vec<Installed> {
+    content
+        .lines()
+        .filter_map(|line| {
+            //The key is quoted and can have `=` in it, like git sources with `?branch=main`.
+            let (key, bins) = line.trim().strip_prefix('"')?.split_once('"')?;
+            let (name, version, source) = parse_key(key)?;
+            let bins = bins.trim().strip_prefix('=')?.trim();
+            let bins = bins.strip_prefix('[')?.strip_suffix(']')?;
+            Some(Installed {
+                name,
+                version,
+                source,
+                bins: bins
+                    .split(',')
+                    .map(|x| x.trim().trim_matches('"'))
+                    .filter(|x| !x.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+                features: Vec::new(),
+                no_default_features: false,
+                all_features: false,
+            })
+        })
+        .collect()
+}
+
+//`{"installs": {"<key>": {"bins": [...], "features": [...], ...}}}`. None if it can't be read.
+pub fn parse_json(content: &str) -> Option<Vec<Installed>> {
+    let json::Value::Object(installs) = json::parse(content)?.get("installs")?.clone() else {
+        return None;
+    };
+    let installed = installs.into_iter().filter_map(|(key, value)| {
+        let (name, version, source) = parse_key(&key)?;
+        Some(Installed {
+            name,
+            version,
+            source,
+            bins: value
+                .get("bins")
+                .map(json::Value::strings)
+                .unwrap_or_default(),
+            features: value
+                .get("features")
+                .map(json::Value::strings)
+                .unwrap_or_default(),
+            no_default_features: value.get("no_default_features") == Some(&json::Value::Bool(true)),
+            all_features: value.get("all_features") == Some(&json::Value::Bool(true)),
+        })
+    });
+    Some(installed.collect())
+}
+
+//.crates2.json has more to say, .crates.toml is read when it's missing or broken. Nothing is
+//recorded when cargo hasn't written either, like in hand-made install roots.
+pub fn load(root: &Path, warnings: &mut Warnings) -> Vec<Installed> {
+    let path = root.join(".crates2.json");
+    if let Some(installed) = warnings
+        .ok(&path, fs::read_to_string(&path))
+        .and_then(|x| parse_json(&x))
+    {
+        return installed;
+    }
+    let path = root.join(".crates.toml");
+    warnings
+        .ok(&path, fs::read_to_string(&path))
+        .map(|x| parse(&x))
+        .unwrap_or_default()
+}
+
+mod test {
+    #[test]
+    fn parse() {
+        let content = r#"[v1]
+"ripgrep 13.0.0 (registry+https://github.com/rust-lang/crates.io-index)" = ["rg"]
+"my-tools 0.1.0 (git+https://github.com/user/my-tools#0f1e2d3c)" = ["a", "b"]
+"lib-only 1.0.0 (path+file:///home/user/lib-only)" = []
+"foo 0.1.0 (git+https://github.com/x/foo?branch=main#abc123)" = ["foo"]
+broken = "line"
+"#;
+        let installed = |name: &str, version: &str, source: &str, bins: &[&str]| super::Installed {
+            name: name.to_string(),
+            version: version.to_string(),
+            source: source.to_string(),
+            bins: bins.iter().map(|x| x.to_string()).collect(),
+            features: Vec::new(),
+            no_default_features: false,
+            all_features: false,
+        };
+        assert_eq!(
+            super::parse(content),
+            vec![
+                installed(
+                    "ripgrep",
+                    "13.0.0",
+                    "registry+https://github.com/rust-lang/crates.io-index",
+                    &["rg"]
+                ),
+                installed(
+                    "my-tools",
+                    "0.1.0",
+                    "git+https://github.com/user/my-tools#0f1e2d3c",
+                    &["a", "b"]
+                ),
+                installed("lib-only", "1.0.0", "path+file:///home/user/lib-only", &[]),
+                installed(
+                    "foo",
+                    "0.1.0",
+                    "git+https://github.com/x/foo?branch=main#abc123",
+                    &["foo"]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_json() {
+        let content = r#"{"installs":{"ripgrep 13.0.0 (registry+https://github.com/rust-lang/crates.io-index)":{"version_req":null,"bins":["rg"],"features":["pcre2"],"all_features":false,"no_default_features":false,"profile":"release","target":"x86_64-unknown-linux-gnu","rustc":"rustc 1.70.0"},"my-tools 0.1.0 (git+https://github.com/user/my-tools#0f1e2d3c)":{"bins":["a"],"no_default_features":true,"all_features":false}}}"#;
+        let installed = super::parse_json(content).unwrap();
+        assert_eq!(installed.len(), 2);
+        assert_eq!(installed[0].name, "ripgrep");
+        assert_eq!(installed[0].bins, ["rg"]);
+        assert_eq!(installed[0].features, ["pcre2"]);
+        assert!(!installed[0].no_default_features);
+        assert!(installed[1].no_default_features);
+        assert!(!installed[1].all_features);
+        assert!(!super::is_local(&installed[0].source));
+        assert_eq!(installed[1].version, "0.1.0");
+        assert!(super::is_local(&installed[1].source));
+        assert_eq!(super::parse_json("{\"installs\": []}"), None);
+        assert_eq!(super::parse_json("not json"), None);
+    }
+}
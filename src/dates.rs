@@ -0,0 +1,102 @@
+//Date formatting without pulling in a date crate. Dates are always in UTC.
+
+use crate::i18n::{plural, tr, Lang};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+//Convert days since 1970-01-01 to (year, month, day).
+//Algorithm from http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+//The (year, month, day) of a point in time.
+pub fn civil(time: SystemTime) -> (i64, i64, i64) {
+    let secs = match time.duration_since(UNIX_EPOCH) {
+        Ok(x) => x.as_secs() as i64,
+        Err(e) => -(e.duration().as_secs() as i64),
+    };
+    civil_from_days(secs.div_euclid(86400))
+}
+
+//Format as YYYY-MM-DD.
+pub fn ymd(time: SystemTime) -> String {
+    let (year, month, day) = civil(time);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+//How long ago, like "3 days ago", in the largest unit that fits. None for times in the future,
+//which a clock set wrong can cause.
+pub fn ago(lang: Lang, time: SystemTime, now: SystemTime) -> Option<String> {
+    let secs = now.duration_since(time).ok()?.as_secs();
+    let units = [
+        (365 * 86400, "time-year"),
+        (30 * 86400, "time-month"),
+        (86400, "time-day"),
+        (3600, "time-hour"),
+        (60, "time-minute"),
+    ];
+    let Some((n, unit)) = units
+        .iter()
+        .map(|(len, unit)| (secs / len, *unit))
+        .find(|(n, _)| *n > 0)
+    else {
+        return Some(tr(lang, "time-now").to_string());
+    };
+    Some(format!(
+        "{n} {} {}",
+        plural(lang, unit, n),
+        tr(lang, "time-ago")
+    ))
+}
+
+mod test {
+    #[test]
+    fn ymd() {
+        use std::time::{Duration, UNIX_EPOCH};
+        let day = |x: u64| UNIX_EPOCH + Duration::from_secs(x * 86400);
+        assert_eq!(super::ymd(UNIX_EPOCH), "1970-01-01");
+        assert_eq!(super::ymd(day(19723)), "2024-01-01");
+        assert_eq!(
+            super::ymd(day(19782) + Duration::from_secs(86399)),
+            "2024-02-29"
+        );
+        assert_eq!(
+            super::ymd(UNIX_EPOCH - Duration::from_secs(1)),
+            "1969-12-31"
+        );
+    }
+
+    #[test]
+    fn ago() {
+        use crate::i18n::Lang;
+        use std::time::{Duration, UNIX_EPOCH};
+        let now = UNIX_EPOCH + Duration::from_secs(19723 * 86400);
+        let ago = |lang, secs| super::ago(lang, now - Duration::from_secs(secs), now);
+        assert_eq!(ago(Lang::En, 30).as_deref(), Some("just now"));
+        assert_eq!(ago(Lang::En, 60).as_deref(), Some("1 minute ago"));
+        assert_eq!(
+            ago(Lang::En, 3 * 86400 + 100).as_deref(),
+            Some("3 days ago")
+        );
+        assert_eq!(ago(Lang::En, 400 * 86400).as_deref(), Some("1 year ago"));
+        assert_eq!(ago(Lang::Pl, 5 * 3600).as_deref(), Some("5 godzin temu"));
+        assert_eq!(
+            ago(Lang::Pl, 2 * 30 * 86400).as_deref(),
+            Some("2 miesiące temu")
+        );
+        assert_eq!(
+            super::ago(Lang::En, now + Duration::from_secs(1), now),
+            None
+        );
+    }
+}
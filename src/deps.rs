@@ -0,0 +1,139 @@
+//Dependencies a crate was built with, read from the Cargo.lock published along with its sources.
+
+use crate::{manifest, PkgInfo};
+use colored::Colorize;
+use std::{collections::BTreeMap, fs};
+
+//Get the name and version of every package in a Cargo.lock, except the crate itself.
+pub fn parse_lock(cargo_lock_content: &str, info: &PkgInfo) -> Vec<(String, String)> {
+    cargo_lock_content
+        .split("[[package]]")
+        .skip(1)
+        .filter_map(|x| {
+            Some((
+                manifest::string(x, "name")?,
+                manifest::string(x, "version")?,
+            ))
+        })
+        .filter(|(name, version)| !(*name == info.name && *version == info.version))
+        .collect()
+}
+
+//None if the crate was published without a Cargo.lock.
+pub fn locked(info: &PkgInfo) -> Option<Vec<(String, String)>> {
+    let content = fs::read_to_string(info.path.join("Cargo.lock")).ok()?;
+    Some(parse_lock(&content, info))
+}
+
+pub fn print(deps: &[(String, String)]) {
+    for (name, version) in deps {
+        println!("{} {}", name.green(), version.yellow());
+    }
+}
+
+//Versions of each dependency used by a and by b, for dependencies where they differ. An empty list
+//means the dependency isn't used at all.
+pub fn diff(
+    a: &[(String, String)],
+    b: &[(String, String)],
+) -> Vec<(String, Vec<String>, Vec<String>)> {
+    let mut versions: BTreeMap<&str, (Vec<String>, Vec<String>)> = BTreeMap::new();
+    for (name, version) in a {
+        versions.entry(name).or_default().0.push(version.clone());
+    }
+    for (name, version) in b {
+        versions.entry(name).or_default().1.push(version.clone());
+    }
+    versions
+        .into_iter()
+        .filter(|(_, (a, b))| a != b)
+        .map(|(name, (a, b))| (name.to_string(), a, b))
+        .collect()
+}
+
+pub fn print_diff(diff: &[(String, Vec<String>, Vec<String>)]) {
+    for (name, a, b) in diff {
+        match (a.is_empty(), b.is_empty()) {
+            (false, true) => println!("{} {} {}", "-".red(), name.red(), a.join(", ")),
+            (true, false) => println!("{} {} {}", "+".green(), name.green(), b.join(", ")),
+            _ => println!(
+                "{} {} {} -> {}",
+                "~".yellow(),
+                name.yellow(),
+                a.join(", "),
+                b.join(", ")
+            ),
+        }
+    }
+}
+
+mod test {
+    #[test]
+    fn parse_lock() {
+        let lock = r#"# This file is automatically @generated by Cargo.
+version = 3
+
+[[package]]
+name = "aho-corasick"
+version = "1.0.2"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "43f6cb1bf222025340178f382c426f13757b2960e89779dfcb319c32542a5a41"
+dependencies = [
+ "memchr",
+]
+
+[[package]]
+name = "memchr"
+version = "2.5.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "ripgrep"
+version = "13.0.0"
+dependencies = [
+ "aho-corasick",
+]
+"#;
+        let info = crate::PkgInfo {
+            name: String::from("ripgrep"),
+            version: String::from("13.0.0"),
+            ..Default::default()
+        };
+        assert_eq!(
+            super::parse_lock(lock, &info),
+            vec![
+                (String::from("aho-corasick"), String::from("1.0.2")),
+                (String::from("memchr"), String::from("2.5.0")),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff() {
+        let pairs = |x: &[(&str, &str)]| -> Vec<(String, String)> {
+            x.iter()
+                .map(|(x, y)| (x.to_string(), y.to_string()))
+                .collect()
+        };
+        let a = pairs(&[
+            ("libc", "0.2.146"),
+            ("memchr", "2.5.0"),
+            ("syn", "1.0.109"),
+            ("syn", "2.0.18"),
+        ]);
+        let b = pairs(&[("libc", "0.2.146"), ("regex", "1.8.4"), ("syn", "2.0.18")]);
+        let strings = |x: &[&str]| -> Vec<String> { x.iter().map(|x| x.to_string()).collect() };
+        assert_eq!(
+            super::diff(&a, &b),
+            vec![
+                (String::from("memchr"), strings(&["2.5.0"]), strings(&[])),
+                (String::from("regex"), strings(&[]), strings(&["1.8.4"])),
+                (
+                    String::from("syn"),
+                    strings(&["1.0.109", "2.0.18"]),
+                    strings(&["2.0.18"])
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,167 @@
+//Recipes installing the same crate versions somewhere else.
+
+use crate::{network, PkgInfo};
+
+//A crate to install, with what it takes to build it the way it was: its features and the registry
+//it came from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Install {
+    //Like `ripgrep@13.0.0`.
+    pub spec: String,
+    pub flags: Vec<String>,
+}
+
+impl Install {
+    pub fn new(info: &PkgInfo) -> Install {
+        let mut flags = Vec::new();
+        //cargo takes the index URL of any registry, which is what it records, while --registry
+        //needs a name from the user's cargo config.
+        if !network::is_crates_io(&info.source) {
+            let index = info
+                .source
+                .strip_prefix("registry+")
+                .unwrap_or(&info.source);
+            flags.extend([String::from("--index"), index.to_string()]);
+        }
+        if !info.features.is_empty() {
+            flags.extend([String::from("--features"), info.features.join(",")]);
+        }
+        if info.no_default_features {
+            flags.push(String::from("--no-default-features"));
+        }
+        if info.all_features {
+            flags.push(String::from("--all-features"));
+        }
+        Install {
+            spec: format!("{}@{}", info.name, info.version),
+            flags,
+        }
+    }
+}
+
+//Crates installed the default way share one cargo install, the others need one each since flags
+//apply to every crate given. Each is the crates and the flags.
+fn commands(installs: &[Install]) -> Vec<(Vec<&str>, String)> {
+    let mut commands = Vec::new();
+    let plain: Vec<&str> = installs
+        .iter()
+        .filter(|x| x.flags.is_empty())
+        .map(|x| x.spec.as_str())
+        .collect();
+    if !plain.is_empty() {
+        commands.push((plain, String::new()));
+    }
+    for x in installs.iter().filter(|x| !x.flags.is_empty()) {
+        commands.push((vec![x.spec.as_str()], format!(" {}", x.flags.join(" "))));
+    }
+    commands
+}
+
+//Binaries without metadata weren't installed from a registry, so they can't be reinstalled and are
+//only mentioned in a comment.
+pub fn dockerfile(installs: &[Install], unknown: &[String]) -> String {
+    let mut out = String::from("FROM rust:latest\n");
+    if !unknown.is_empty() {
+        out += &format!(
+            "# Not installed from a registry, add them manually: {}\n",
+            unknown.join(", ")
+        );
+    }
+    for (specs, flags) in commands(installs) {
+        out += &format!("RUN cargo install --locked{flags}");
+        for spec in specs {
+            out += &format!(" \\\n    {spec}");
+        }
+        out += "\n";
+    }
+    out
+}
+
+//devcontainer.json allows comments, which is where binaries without metadata go.
+pub fn devcontainer(installs: &[Install], unknown: &[String]) -> String {
+    let mut out = String::from("{\n");
+    if !unknown.is_empty() {
+        out += &format!(
+            "  // Not installed from a registry, add them manually: {}\n",
+            unknown.join(", ")
+        );
+    }
+    out += "  \"features\": {\n    \"ghcr.io/devcontainers/features/rust:1\": {}\n  }";
+    let commands: Vec<String> = commands(installs)
+        .iter()
+        .map(|(specs, flags)| format!("cargo install --locked{flags} {}", specs.join(" ")))
+        .collect();
+    if !commands.is_empty() {
+        out += &format!(",\n  \"postCreateCommand\": \"{}\"", commands.join(" && "));
+    }
+    out += "\n}\n";
+    out
+}
+
+mod test {
+    #[cfg(test)]
+    fn installs() -> Vec<super::Install> {
+        let info = |name: &str, version: &str| crate::PkgInfo {
+            name: name.to_string(),
+            version: version.to_string(),
+            source: String::from("registry+https://github.com/rust-lang/crates.io-index"),
+            ..Default::default()
+        };
+        let rg = crate::PkgInfo {
+            features: vec![String::from("pcre2")],
+            no_default_features: true,
+            ..info("ripgrep", "13.0.0")
+        };
+        let internal = crate::PkgInfo {
+            source: String::from("sparse+https://crates.example.com/index/"),
+            ..info("internal", "0.1.0")
+        };
+        [info("just", "1.2.3"), rg, info("jless", "0.9.0"), internal]
+            .iter()
+            .map(super::Install::new)
+            .collect()
+    }
+
+    #[test]
+    fn install() {
+        let installs = installs();
+        assert_eq!(installs[0].spec, "just@1.2.3");
+        assert!(installs[0].flags.is_empty());
+        assert_eq!(
+            installs[1].flags,
+            ["--features", "pcre2", "--no-default-features"]
+        );
+        assert_eq!(
+            installs[3].flags,
+            ["--index", "sparse+https://crates.example.com/index/"]
+        );
+    }
+
+    #[test]
+    fn dockerfile() {
+        assert_eq!(
+            super::dockerfile(&installs(), &[String::from("my-script")]),
+            "FROM rust:latest\n# Not installed from a registry, add them manually: my-script\nRUN cargo install --locked \\\n    just@1.2.3 \\\n    jless@0.9.0\nRUN cargo install --locked --features pcre2 --no-default-features \\\n    ripgrep@13.0.0\nRUN cargo install --locked --index sparse+https://crates.example.com/index/ \\\n    internal@0.1.0\n"
+        );
+        assert_eq!(super::dockerfile(&[], &[]), "FROM rust:latest\n");
+    }
+
+    #[test]
+    fn devcontainer() {
+        assert_eq!(
+            super::devcontainer(&installs()[..2], &[String::from("my-script")]),
+            r#"{
+  // Not installed from a registry, add them manually: my-script
+  "features": {
+    "ghcr.io/devcontainers/features/rust:1": {}
+  },
+  "postCreateCommand": "cargo install --locked just@1.2.3 && cargo install --locked --features pcre2 --no-default-features ripgrep@13.0.0"
+}
+"#
+        );
+        assert_eq!(
+            super::devcontainer(&[], &[]),
+            "{\n  \"features\": {\n    \"ghcr.io/devcontainers/features/rust:1\": {}\n  }\n}\n"
+        );
+    }
+}
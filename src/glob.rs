@@ -0,0 +1,84 @@
+//Shell-style patterns for picking crates by name, like `cargo-*`. `*` matches any run of characters
+//and `?` any one character.
+
+pub fn is_pattern(name: &str) -> bool {
+    name.contains(['*', '?'])
+}
+
+pub fn matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    matches_chars(&pattern, &name)
+}
+
+fn matches_chars(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|i| matches_chars(rest, &name[i..])),
+        Some(('?', rest)) => !name.is_empty() && matches_chars(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && matches_chars(rest, &name[1..]),
+    }
+}
+
+//Replace patterns with the names they match, in the order given, without duplicates. Other names
+//are kept as they are. The first pattern that matches nothing is the error.
+pub fn expand<'a>(names: &'a [String], candidates: &[&str]) -> Result<Vec<String>, &'a str> {
+    let mut expanded: Vec<String> = Vec::new();
+    for name in names {
+        let matched: Vec<&str> = if is_pattern(name) {
+            let mut matched: Vec<&str> = candidates
+                .iter()
+                .copied()
+                .filter(|x| matches(name, x))
+                .collect();
+            matched.sort();
+            if matched.is_empty() {
+                return Err(name);
+            }
+            matched
+        } else {
+            vec![name]
+        };
+        for x in matched {
+            if !expanded.iter().any(|y| y == x) {
+                expanded.push(x.to_string());
+            }
+        }
+    }
+    Ok(expanded)
+}
+
+mod test {
+    #[test]
+    fn matches() {
+        use super::matches;
+        assert!(matches("cargo-*", "cargo-edit"));
+        assert!(matches("cargo-*", "cargo-"));
+        assert!(!matches("cargo-*", "cargo"));
+        assert!(matches("*grep", "ripgrep"));
+        assert!(matches("r?", "rg"));
+        assert!(!matches("r?", "rga"));
+        assert!(matches("*", ""));
+        assert!(matches("j*s*", "jless"));
+        assert!(!matches("just", "jus"));
+    }
+
+    #[test]
+    fn expand() {
+        let names = |x: &[&str]| -> Vec<String> { x.iter().map(|x| x.to_string()).collect() };
+        let candidates = ["cargo-edit", "rg", "cargo-watch", "ripgrep"];
+        assert_eq!(
+            super::expand(&names(&["rg", "cargo-*", "cargo-edit"]), &candidates),
+            Ok(names(&["rg", "cargo-edit", "cargo-watch"]))
+        );
+        //Names that aren't patterns are checked by the command.
+        assert_eq!(
+            super::expand(&names(&["fd"]), &candidates),
+            Ok(names(&["fd"]))
+        );
+        assert_eq!(
+            super::expand(&names(&["rg", "fd-*"]), &candidates),
+            Err("fd-*")
+        );
+    }
+}
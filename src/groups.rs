@@ -0,0 +1,219 @@
+//Group installed crates by their crates.io categories to spot tools with overlapping functionality.
+
+use crate::{
+    i18n::{tr, Lang},
+    locale::Style,
+    PkgInfo,
+};
+use colored::Colorize;
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+//Crates without categories are grouped by their keywords (prefixed with '#') instead.
+//Groups with more than one crate come first, biggest first.
+pub fn group<'a>(infos: impl IntoIterator<Item = &'a PkgInfo>) -> Vec<(String, Vec<&'a PkgInfo>)> {
+    let mut groups: BTreeMap<String, Vec<&PkgInfo>> = BTreeMap::new();
+    for info in infos {
+        let keys: Vec<String> = if info.categories.is_empty() {
+            info.keywords.iter().map(|x| format!("#{x}")).collect()
+        } else {
+            info.categories.clone()
+        };
+        for key in keys {
+            let group = groups.entry(key).or_default();
+            //A crate providing several binaries shows up once per binary.
+            if !group.iter().any(|x| x.name == info.name) {
+                group.push(info);
+            }
+        }
+    }
+
+    let mut groups: Vec<_> = groups.into_iter().collect();
+    groups.sort_by_key(|(_, crates)| std::cmp::Reverse(crates.len()));
+    groups
+}
+
+pub fn print(groups: &[(String, Vec<&PkgInfo>)], lang: Lang, style: Style) {
+    for (key, crates) in groups {
+        if crates.len() > 1 {
+            println!(
+                "{} ({} {})",
+                key.red().bold(),
+                style.count(crates.len()),
+                tr(lang, "msg-overlapping")
+            );
+        } else {
+            println!("{}", key.green().bold());
+        }
+        for info in crates {
+            println!("\t{} - {}", info.name.bold(), info.description.blue());
+        }
+    }
+}
+
+//Message keys of the headings for the categorized listing, more specific categories first.
+//Subcategories are matched by their parent too.
+const HEADINGS: [(&str, &str); 12] = [
+    ("development-tools::cargo-plugins", "heading-cargo"),
+    ("development-tools::build-utils", "heading-build"),
+    ("development-tools::testing", "heading-testing"),
+    ("development-tools::debugging", "heading-debugging"),
+    ("development-tools::profiling", "heading-profiling"),
+    ("development-tools", "heading-development"),
+    ("text-editors", "heading-editors"),
+    ("text-processing", "heading-text"),
+    ("filesystem", "heading-filesystem"),
+    ("network-programming", "heading-networking"),
+    ("web-programming", "heading-web"),
+    ("command-line-utilities", "heading-cli"),
+];
+
+//Kinds of tools crates.io has no category for.
+const KEYWORD_HEADINGS: [(&str, &str); 4] = [
+    ("tui", "heading-tui"),
+    ("lint", "heading-linters"),
+    ("linter", "heading-linters"),
+    ("linting", "heading-linters"),
+];
+
+//Message key of the heading for crates nothing else fits, which is listed last.
+pub const OTHER: &str = "heading-other";
+
+pub fn path() -> Option<PathBuf> {
+    Some(crate::config_dir()?.join("categories"))
+}
+
+//Headings set by the user, as `name = "Heading"` lines in the config directory, by binary or crate
+//name.
+pub fn load_headings() -> Vec<(String, String)> {
+    let Some(path) = path() else {
+        return Vec::new();
+    };
+    fs::read_to_string(path)
+        .map(|x| crate::config::parse_pairs(&x))
+        .unwrap_or_default()
+}
+
+//The heading a crate goes under in the language of the messages. Headings set by the user are used
+//as written.
+pub fn heading(
+    bin: &str,
+    info: Option<&PkgInfo>,
+    custom: &[(String, String)],
+    lang: Lang,
+) -> String {
+    let names = [Some(bin), info.map(|x| x.name.as_str())];
+    if let Some((_, heading)) = custom
+        .iter()
+        .find(|(name, _)| names.contains(&Some(name.as_str())))
+    {
+        return heading.clone();
+    }
+    if bin.starts_with("cargo-") {
+        return tr(lang, "heading-cargo").to_string();
+    }
+    let Some(info) = info else {
+        return tr(lang, OTHER).to_string();
+    };
+    if let Some((_, heading)) = KEYWORD_HEADINGS
+        .iter()
+        .find(|(keyword, _)| info.keywords.iter().any(|x| x == keyword))
+    {
+        return tr(lang, heading).to_string();
+    }
+    let matches = |category: &str, x: &str| {
+        x == category
+            || x.strip_prefix(category)
+                .is_some_and(|x| x.starts_with("::"))
+    };
+    if let Some((_, heading)) = HEADINGS
+        .iter()
+        .find(|(category, _)| info.categories.iter().any(|x| matches(category, x)))
+    {
+        return tr(lang, heading).to_string();
+    }
+    //Any other category makes a heading too, "date-and-time" becomes "Date and time".
+    match info.categories.first() {
+        Some(category) => {
+            let category = category
+                .split("::")
+                .next()
+                .unwrap_or_default()
+                .replace('-', " ");
+            let mut chars = category.chars();
+            chars
+                .next()
+                .map(|x| x.to_uppercase().chain(chars).collect())
+                .unwrap_or_else(|| tr(lang, OTHER).to_string())
+        }
+        None => tr(lang, OTHER).to_string(),
+    }
+}
+
+mod test {
+    #[test]
+    fn group() {
+        let info = |name: &str, categories: &[&str], keywords: &[&str]| crate::PkgInfo {
+            name: name.to_string(),
+            categories: categories.iter().map(|x| x.to_string()).collect(),
+            keywords: keywords.iter().map(|x| x.to_string()).collect(),
+            ..Default::default()
+        };
+        let jq = info("jaq", &["command-line-utilities"], &["json"]);
+        let jless = info("jless", &["command-line-utilities"], &[]);
+        let rg = info("ripgrep", &["text-processing"], &[]);
+        let fx = info("fx", &[], &["json", "viewer"]);
+        let none = info("none", &[], &[]);
+
+        //jless is listed twice, like a crate with two binaries.
+        let groups = super::group([&jq, &jless, &rg, &fx, &none, &jless]);
+        let names: Vec<(&str, Vec<&str>)> = groups
+            .iter()
+            .map(|(key, crates)| {
+                (
+                    key.as_str(),
+                    crates.iter().map(|x| x.name.as_str()).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("command-line-utilities", vec!["jaq", "jless"]),
+                ("#json", vec!["fx"]),
+                ("#viewer", vec!["fx"]),
+                ("text-processing", vec!["ripgrep"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn heading() {
+        let info = |categories: &[&str], keywords: &[&str]| crate::PkgInfo {
+            name: String::from("tool"),
+            categories: categories.iter().map(|x| x.to_string()).collect(),
+            keywords: keywords.iter().map(|x| x.to_string()).collect(),
+            ..Default::default()
+        };
+        let en = crate::i18n::Lang::En;
+        let heading = |bin, info: &crate::PkgInfo| super::heading(bin, Some(info), &[], en);
+        let cli = info(
+            &["command-line-utilities", "development-tools::testing"],
+            &[],
+        );
+        assert_eq!(heading("tool", &cli), "Testing");
+        let tui = info(&["command-line-utilities"], &["tui"]);
+        assert_eq!(heading("tool", &tui), "TUI apps");
+        assert_eq!(heading("cargo-tool", &tui), "Cargo subcommands");
+        let http = info(&["web-programming::http-client"], &[]);
+        assert_eq!(heading("tool", &http), "Web");
+        let date = info(&["date-and-time"], &[]);
+        assert_eq!(heading("tool", &date), "Date and time");
+        assert_eq!(heading("tool", &info(&[], &[])), "Other");
+        assert_eq!(super::heading("script", None, &[], en), "Other");
+        let custom = [(String::from("tool"), String::from("Favourites"))];
+        assert_eq!(super::heading("t", Some(&cli), &custom, en), "Favourites");
+        let pl = crate::i18n::Lang::Pl;
+        assert_eq!(super::heading("tool", Some(&tui), &[], pl), "Aplikacje TUI");
+        assert_eq!(super::heading("script", None, &[], pl), "Inne");
+    }
+}
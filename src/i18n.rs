@@ -0,0 +1,515 @@
+//User facing strings in every supported language.
+//Messages are looked up by key; a missing key is returned as is, so it's easy to spot.
+
+use crate::locale;
+
+#[derive(PartialEq, Debug, Default, Clone, Copy)]
+pub enum Lang {
+    #[default]
+    En,
+    Pl,
+}
+
+impl Lang {
+    //Parse a language code or a full locale name like "pl_PL.UTF-8".
+    pub fn from_locale(locale: &str) -> Option<Lang> {
+        let code = locale.split(['_', '.', '@', '-']).next()?;
+        match code.to_lowercase().as_str() {
+            "en" | "c" | "posix" => Some(Lang::En),
+            "pl" => Some(Lang::Pl),
+            _ => None,
+        }
+    }
+
+    //Detect the language from the locale set for messages, falling back to English.
+    pub fn detect() -> Lang {
+        locale::name("LC_MESSAGES")
+            .and_then(|x| Lang::from_locale(&x))
+            .unwrap_or_default()
+    }
+}
+
+//Order: key, English, Polish.
+const MESSAGES: &[(&str, &str, &str)] = &[
+    ("help-usage", "Usage:", "Użycie:"),
+    ("help-options", "OPTIONS", "OPCJE"),
+    (
+        "help-list",
+        "list installed crates, the same as without a command",
+        "wypisz zainstalowane pakiety, tak samo jak bez polecenia",
+    ),
+    (
+        "help-debug-info",
+        "print the environment, to paste into bug reports",
+        "wypisz środowisko do wklejenia w zgłoszeniu błędu",
+    ),
+    (
+        "help-cheatsheet",
+        "print a markdown (or HTML) page listing installed tools with descriptions and homepages",
+        "wypisz stronę w markdown (lub HTML) z zainstalowanymi narzędziami, ich opisami i stronami domowymi",
+    ),
+    (
+        "help-check",
+        "compare installed crates with a toolbox manifest",
+        "porównaj zainstalowane pakiety z listą wymaganych narzędzi",
+    ),
+    (
+        "help-deps",
+        "list the dependency versions locked in the crate's Cargo.lock",
+        "wypisz wersje zależności zapisane w Cargo.lock pakietu",
+    ),
+    (
+        "help-deps-diff",
+        "compare the locked dependencies of two crates",
+        "porównaj zależności zapisane w Cargo.lock dwóch pakietów",
+    ),
+    (
+        "help-export-dockerfile",
+        "print a Dockerfile installing the same crate versions",
+        "wypisz Dockerfile instalujący te same wersje pakietów",
+    ),
+    (
+        "help-export-devcontainer",
+        "print a devcontainer.json installing the same crate versions",
+        "wypisz devcontainer.json instalujący te same wersje pakietów",
+    ),
+    (
+        "help-groups",
+        "group crates by category and highlight overlapping tools",
+        "pogrupuj pakiety według kategorii i wyróżnij te o podobnym przeznaczeniu",
+    ),
+    (
+        "help-info",
+        "show everything known about an installed crate",
+        "pokaż wszystko, co wiadomo o zainstalowanym pakiecie",
+    ),
+    (
+        "help-man",
+        "show the man page of a crate's binary, or its --help output",
+        "pokaż stronę podręcznika programu z pakietu albo wynik jego --help",
+    ),
+    (
+        "help-star",
+        "mark a crate as starred",
+        "oznacz pakiet gwiazdką",
+    ),
+    (
+        "help-unstar",
+        "remove the star from a crate",
+        "usuń gwiazdkę z pakietu",
+    ),
+    (
+        "help-uninstall",
+        "uninstall crates with cargo, or remove binaries cargo has no record of. What patterns like 'cargo-*' match and binaries to delete are listed and confirmed first, unless --yes is given",
+        "odinstaluj pakiety przez cargo lub usuń pliki, których cargo nie zapisało. To, co pasuje do wzorców jak 'cargo-*', i pliki do usunięcia są najpierw wypisywane do potwierdzenia, chyba że podano --yes",
+    ),
+    (
+        "help-update",
+        "reinstall crates that have a newer version on crates.io, all of them or the ones given, or matching patterns like 'cargo-*'",
+        "zainstaluj ponownie pakiety, które mają nowszą wersję na crates.io, wszystkie lub podane albo pasujące do wzorców jak 'cargo-*'",
+    ),
+    (
+        "help-update-target",
+        "build the installed crates given, or '*' for all of them, for another target, into <root>/targets/<triple>",
+        "zbuduj podane zainstalowane pakiety lub '*' dla wszystkich dla innej platformy, w <root>/targets/<triple>",
+    ),
+    ("help-help", "print help", "wypisz pomoc"),
+    ("help-versions", "print versions", "wypisz wersje"),
+    ("help-descs", "print descriptions", "wypisz opisy"),
+    (
+        "help-timing",
+        "print how long each phase took (to stderr)",
+        "wypisz czas trwania każdego etapu (na stderr)",
+    ),
+    (
+        "help-plain",
+        "print one \"label: value\" line per field, without colors",
+        "wypisz każde pole w osobnej linii \"etykieta: wartość\", bez kolorów",
+    ),
+    (
+        "help-starred",
+        "only list starred crates",
+        "wypisz tylko pakiety oznaczone gwiazdką",
+    ),
+    (
+        "help-by-category",
+        "list crates under headings like build tools or cargo subcommands, inferred from their categories",
+        "wypisz pakiety pod nagłówkami, np. narzędzia budowania czy podpolecenia cargo, na podstawie ich kategorii",
+    ),
+    (
+        "help-build-info",
+        "print the compiler version and build date of each binary",
+        "wypisz wersję kompilatora i datę kompilacji każdego programu",
+    ),
+    (
+        "help-all-files",
+        "also list dotfiles and other non-binaries found in bin directories",
+        "wypisz też pliki ukryte i inne pliki niebędące programami z katalogów bin",
+    ),
+    (
+        "help-locked",
+        "update with or without --locked, remembered for the crates updated (with it by default)",
+        "aktualizuj z --locked lub bez, zapamiętane dla aktualizowanych pakietów (domyślnie z)",
+    ),
+    (
+        "help-no-progress",
+        "don't draw a progress bar while scanning",
+        "nie pokazuj paska postępu podczas skanowania",
+    ),
+    (
+        "help-verbose",
+        "list every path that couldn't be read while scanning",
+        "wypisz każdą ścieżkę, której nie udało się odczytać podczas skanowania",
+    ),
+    (
+        "help-compare-system",
+        "show which crates are also installed with apt, brew or pacman and compare versions",
+        "pokaż, które pakiety zainstalowano też przez apt, brew lub pacman, i porównaj wersje",
+    ),
+    (
+        "help-outdated",
+        "show crates with a newer version on crates.io (needs curl)",
+        "pokaż pakiety, które mają nowszą wersję na crates.io (wymaga curl)",
+    ),
+    (
+        "help-docs",
+        "print the documentation URL of each crate",
+        "wypisz adres dokumentacji każdego pakietu",
+    ),
+    (
+        "help-wide",
+        "print the homepage and repository URLs under each crate",
+        "wypisz adresy strony domowej i repozytorium pod każdym pakietem",
+    ),
+    (
+        "help-all-source-versions",
+        "also print versions in registry/src other than the installed one",
+        "wypisz też wersje w registry/src inne niż zainstalowana",
+    ),
+    (
+        "help-bin-names",
+        "list binaries by their own name, followed by the crate's when it differs (default)",
+        "wypisz programy pod ich nazwą, a za nią nazwę pakietu, jeśli jest inna (domyślnie)",
+    ),
+    (
+        "help-crate-names",
+        "list binaries by the name of their crate, followed by their own when it differs",
+        "wypisz programy pod nazwą ich pakietu, a za nią ich nazwę, jeśli jest inna",
+    ),
+    (
+        "help-version",
+        "print the version, git commit, build date and enabled features",
+        "wypisz wersję, commit, datę kompilacji i włączone funkcje",
+    ),
+    (
+        "help-json",
+        "print the listing (or --version) as JSON",
+        "wypisz listę (lub --version) jako JSON",
+    ),
+    (
+        "help-iso",
+        "write dates and numbers as 2024-01-01 and 12345 whatever the locale",
+        "zapisuj daty i liczby jako 2024-01-01 i 12345 niezależnie od ustawień regionalnych",
+    ),
+    (
+        "help-absolute-time",
+        "print dates instead of times like \"3 days ago\" in a terminal",
+        "wypisuj daty zamiast czasu w rodzaju \"3 dni temu\" w terminalu",
+    ),
+    (
+        "help-width",
+        "fit the listing into this many columns instead of COLUMNS",
+        "zmieść listę w podanej liczbie kolumn zamiast COLUMNS",
+    ),
+    (
+        "help-lang",
+        "use the given language (en, pl) instead of the one from the locale",
+        "użyj podanego języka (en, pl) zamiast ustawionego w locale",
+    ),
+    ("help-examples", "Examples", "Przykłady"),
+    (
+        "help-example-v",
+        "print package names and versions",
+        "wypisz nazwy i wersje pakietów",
+    ),
+    (
+        "help-example-d",
+        "print package names and descriptions",
+        "wypisz nazwy i opisy pakietów",
+    ),
+    (
+        "help-example-vd",
+        "print package names, descriptions and versions",
+        "wypisz nazwy, opisy i wersje pakietów",
+    ),
+    (
+        "help-exit-codes",
+        "Exit codes:\n\t0 success\n\t1 error\n\t2 no cargo install root found\n\t3 nothing installed\n\t4 check found missing or mismatched crates\n\t5 network failure",
+        "Kody wyjścia:\n\t0 sukces\n\t1 błąd\n\t2 nie znaleziono katalogu instalacji cargo\n\t3 nic nie zainstalowano\n\t4 check znalazł brakujące lub złe wersje pakietów\n\t5 błąd sieci",
+    ),
+    (
+        "help-note",
+        "Note:\nInvalid arguments will be ignored.",
+        "Uwaga:\nNieprawidłowe argumenty są ignorowane.",
+    ),
+    ("heading-cargo", "Cargo subcommands", "Podpolecenia cargo"),
+    ("heading-build", "Build tools", "Narzędzia do budowania"),
+    ("heading-testing", "Testing", "Testowanie"),
+    ("heading-debugging", "Debugging", "Debugowanie"),
+    ("heading-profiling", "Profiling", "Profilowanie"),
+    (
+        "heading-development",
+        "Development tools",
+        "Narzędzia programistyczne",
+    ),
+    ("heading-editors", "Editors", "Edytory"),
+    ("heading-text", "Text processing", "Przetwarzanie tekstu"),
+    ("heading-filesystem", "Filesystem", "System plików"),
+    ("heading-networking", "Networking", "Sieć"),
+    ("heading-web", "Web", "WWW"),
+    ("heading-cli", "Command-line utilities", "Narzędzia wiersza poleceń"),
+    ("heading-tui", "TUI apps", "Aplikacje TUI"),
+    ("heading-linters", "Linters", "Lintery"),
+    ("heading-other", "Other", "Inne"),
+    ("label-name", "name", "nazwa"),
+    ("label-crate", "crate", "pakiet"),
+    ("label-binary", "binary", "program"),
+    ("label-version", "version", "wersja"),
+    ("label-description", "description", "opis"),
+    ("label-documentation", "documentation", "dokumentacja"),
+    ("label-homepage", "homepage", "strona domowa"),
+    ("label-repository", "repository", "repozytorium"),
+    ("label-source", "source", "źródło"),
+    ("label-features", "features", "funkcje"),
+    ("label-root", "install root", "katalog instalacji"),
+    ("label-binaries", "binaries", "programy"),
+    ("label-installed", "installed", "zainstalowano"),
+    ("label-other-versions", "other versions in sources", "inne wersje w źródłach"),
+    ("label-compiler", "compiler", "kompilator"),
+    ("label-built", "built", "skompilowano"),
+    ("label-installed-with", "installed with", "zainstalowano przez"),
+    ("label-starred", "starred", "oznaczony"),
+    ("yes", "yes", "tak"),
+    ("time-now", "just now", "przed chwilą"),
+    ("time-ago", "ago", "temu"),
+    ("time-minute", "minute|minutes", "minutę|minuty|minut"),
+    ("time-hour", "hour|hours", "godzinę|godziny|godzin"),
+    ("time-day", "day|days", "dzień|dni|dni"),
+    ("time-month", "month|months", "miesiąc|miesiące|miesięcy"),
+    ("time-year", "year|years", "rok|lata|lat"),
+    (
+        "msg-overlapping",
+        "overlapping tools",
+        "narzędzia o podobnym przeznaczeniu",
+    ),
+    (
+        "msg-no-system",
+        "None of the crates are installed with apt, brew or pacman.",
+        "Żadnego z pakietów nie zainstalowano przez apt, brew ani pacman.",
+    ),
+    (
+        "msg-up-to-date",
+        "All crates are up to date.",
+        "Wszystkie pakiety są aktualne.",
+    ),
+    ("msg-updating", "Updating", "Aktualizowanie"),
+    ("msg-uninstalling", "Uninstalling", "Odinstalowywanie"),
+    ("msg-removing", "Removing", "Usuwanie"),
+    ("msg-confirm-uninstall", "Uninstall these? [y/N]", "Odinstalować te pakiety? [t/N]"),
+    ("msg-uninstall-cancelled", "Nothing was uninstalled.", "Nic nie zostało odinstalowane."),
+    ("msg-update-summary", "Summary", "Podsumowanie"),
+    ("msg-updated", "updated", "zaktualizowano"),
+    ("msg-update-failed", "failed", "niepowodzenie"),
+    ("system-same", "same version", "ta sama wersja"),
+    ("system-newer", "newer than cargo's", "nowsza niż z cargo"),
+    ("system-older", "older than cargo's", "starsza niż z cargo"),
+    ("check-missing", "missing", "brakujący"),
+    ("check-mismatched", "wrong version", "zła wersja"),
+    ("check-wanted", "wanted", "wymagana"),
+    ("check-extra", "extra", "dodatkowy"),
+    (
+        "check-ok",
+        "All crates from the manifest are installed.",
+        "Wszystkie wymagane pakiety są zainstalowane.",
+    ),
+    ("msg-starred", "Starred", "Oznaczono gwiazdką"),
+    ("msg-unstarred", "Unstarred", "Usunięto gwiazdkę"),
+    ("msg-unchanged", "Nothing to do for", "Bez zmian dla"),
+    ("msg-did-you-mean", "Did you mean", "Czy chodziło o"),
+    (
+        "err-no-root",
+        "Failed to locate cargo root.",
+        "Nie udało się znaleźć katalogu głównego cargo.",
+    ),
+    (
+        "err-no-pkgs",
+        "Failed to list packages.",
+        "Nie udało się wylistować pakietów.",
+    ),
+    ("err-not-installed", "Not installed", "Nie zainstalowano"),
+    (
+        "err-stars-write",
+        "Failed to save starred crates",
+        "Nie udało się zapisać oznaczonych pakietów",
+    ),
+    (
+        "err-check-no-manifest",
+        "Pass the manifest to check against with --against <file>.",
+        "Podaj listę wymaganych narzędzi przez --against <plik>.",
+    ),
+    (
+        "err-no-lock",
+        "The crate was published without a Cargo.lock",
+        "Pakiet opublikowano bez pliku Cargo.lock",
+    ),
+    (
+        "err-export-format",
+        "Choose the export format: --dockerfile or --devcontainer.",
+        "Wybierz format eksportu: --dockerfile lub --devcontainer.",
+    ),
+    (
+        "err-crash",
+        "cargo-ls-crates crashed. This is a bug.",
+        "cargo-ls-crates uległ awarii. To błąd w programie.",
+    ),
+    (
+        "msg-crash-report",
+        "Please report it with the above and the output of `cargo ls-crates doctor` to a new issue at https://github.com/bejker123/cargo-crates/issues",
+        "Zgłoś go, dołączając powyższe i wynik `cargo ls-crates doctor`, w nowym zgłoszeniu na https://github.com/bejker123/cargo-crates/issues",
+    ),
+    (
+        "msg-crash-log",
+        "Backtrace saved to",
+        "Ślad stosu zapisano w",
+    ),
+    ("err-read", "Failed to read", "Nie udało się odczytać"),
+    (
+        "err-uninstall-no-names",
+        "Name the crates or binaries to uninstall.",
+        "Podaj pakiety lub pliki do odinstalowania.",
+    ),
+    (
+        "err-update-target-no-names",
+        "Name the crates to build for the target, or '*' for all of them.",
+        "Podaj pakiety do zbudowania dla platformy lub '*' dla wszystkich.",
+    ),
+    ("err-uninstall", "Failed to uninstall", "Nie udało się odinstalować"),
+    ("err-fetch", "Failed to download", "Nie udało się pobrać"),
+    (
+        "err-insecure-url",
+        "Only HTTPS URLs are supported, so the manifest can't be changed on the way",
+        "Obsługiwane są tylko adresy HTTPS, żeby manifestu nie dało się podmienić po drodze",
+    ),
+    (
+        "err-checksum",
+        "The manifest doesn't have the expected SHA-256",
+        "Manifest nie ma oczekiwanej sumy SHA-256",
+    ),
+    ("err-run", "Failed to run", "Nie udało się uruchomić"),
+    ("warn-label", "warning", "ostrzeżenie"),
+    (
+        "warn-unreadable",
+        "some entries may be missing, unreadable paths",
+        "może brakować niektórych pozycji, nieodczytane ścieżki",
+    ),
+    ("warn-registry", "couldn't read registry", "nie udało się odczytać rejestru"),
+    (
+        "warn-registry-details",
+        "details of crates from it are missing",
+        "brakuje szczegółów pakietów z niego",
+    ),
+    (
+        "warn-no-latest",
+        "couldn't look up the latest version of",
+        "nie udało się sprawdzić najnowszej wersji",
+    ),
+    (
+        "warn-locked",
+        "couldn't remember whether to update with --locked",
+        "nie udało się zapamiętać, czy aktualizować z --locked",
+    ),
+    (
+        "warn-cargo-version",
+        "couldn't record the cargo version that installed",
+        "nie udało się zapisać wersji cargo, która zainstalowała",
+    ),
+    (
+        "warn-not-program",
+        "skipped, the file is empty or not executable (--all-files lists it anyway)",
+        "pominięto, plik jest pusty lub niewykonywalny (--all-files i tak go wypisze)",
+    ),
+    (
+        "warn-verbose-hint",
+        "Rerun with --verbose to see which ones.",
+        "Uruchom ponownie z --verbose, aby zobaczyć które.",
+    ),
+    (
+        "warn-long-path",
+        "the path is too long, on Windows enable long path support",
+        "ścieżka jest za długa, w systemie Windows włącz obsługę długich ścieżek",
+    ),
+    (
+        "err-no-info",
+        "Failed to get info.",
+        "Nie udało się pobrać informacji o pakietach.",
+    ),
+];
+
+//Messages with plural forms keep them separated by '|': singular and plural in English, and in
+//Polish the forms for 1, for 2-4 (except 12-14, also 22-24 etc.) and for everything else.
+pub fn plural(lang: Lang, key: &'static str, n: u64) -> &'static str {
+    let forms: Vec<&str> = tr(lang, key).split('|').collect();
+    let form = match lang {
+        _ if n == 1 => 0,
+        Lang::En => 1,
+        Lang::Pl if (2..=4).contains(&(n % 10)) && !(12..=14).contains(&(n % 100)) => 1,
+        Lang::Pl => 2,
+    };
+    forms.get(form).or(forms.last()).copied().unwrap_or(key)
+}
+
+pub fn tr(lang: Lang, key: &'static str) -> &'static str {
+    let Some((_, en, pl)) = MESSAGES.iter().find(|(k, _, _)| *k == key) else {
+        return key;
+    };
+    match lang {
+        Lang::En => en,
+        Lang::Pl => pl,
+    }
+}
+
+mod test {
+    #[test]
+    fn from_locale() {
+        use super::Lang;
+        assert_eq!(Lang::from_locale("pl_PL.UTF-8"), Some(Lang::Pl));
+        assert_eq!(Lang::from_locale("en_US"), Some(Lang::En));
+        assert_eq!(Lang::from_locale("PL"), Some(Lang::Pl));
+        assert_eq!(Lang::from_locale("C"), Some(Lang::En));
+        assert_eq!(Lang::from_locale("de_DE.UTF-8"), None);
+        assert_eq!(Lang::from_locale(""), None);
+    }
+
+    #[test]
+    fn tr() {
+        use super::{tr, Lang};
+        assert_eq!(tr(Lang::En, "help-usage"), "Usage:");
+        assert_eq!(tr(Lang::Pl, "help-usage"), "Użycie:");
+        assert_eq!(tr(Lang::Pl, "no-such-key"), "no-such-key");
+    }
+
+    #[test]
+    fn plural() {
+        use super::{plural, Lang};
+        assert_eq!(plural(Lang::En, "time-day", 1), "day");
+        assert_eq!(plural(Lang::En, "time-day", 3), "days");
+        let pl: Vec<&str> = [1, 2, 5, 12, 22, 25]
+            .iter()
+            .map(|x| plural(Lang::Pl, "time-hour", *x))
+            .collect();
+        assert_eq!(
+            pl,
+            ["godzinę", "godziny", "godzin", "godzin", "godziny", "godzin"]
+        );
+    }
+}
@@ -0,0 +1,500 @@
+//Finding install roots, the binaries in their bin directories and the metadata of the crates they
+//came from.
+
+use crate::{
+    aliases, crates_toml, manifest, paths, progress::Progress, version, warnings::Warnings,
+};
+use std::{
+    collections::HashMap,
+    env, fs,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+/// An install root that exists, and the environment variable it was found through.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InstallRoot {
+    /// The root, with `bin` and `registry` in it.
+    pub path: PathBuf,
+    /// `CARGO_INSTALL_ROOT`, `CARGO_HOME` or `HOME`.
+    pub variable: &'static str,
+    /// The target of a root `update --target` built into, `<root>/targets/<triple>` in the root
+    /// the variable points to.
+    pub target: Option<String>,
+}
+
+//Candidate Install Root Directories paired with the environment variable each one comes from. Each
+//is followed by the roots update --target built into inside it, with their target.
+pub fn install_dir_candidates() -> Vec<(&'static str, Option<String>, Option<PathBuf>)> {
+    //According to cargo documentation it's best to start looking for the Install Root Directory in
+    //this order:
+    let candidates = [
+        (
+            "CARGO_INSTALL_ROOT",
+            env::var_os("CARGO_INSTALL_ROOT").map(PathBuf::from),
+        ),
+        ("CARGO_HOME", env::var_os("CARGO_HOME").map(PathBuf::from)),
+        ("HOME", paths::home_dir().map(|x| x.join(".cargo"))),
+    ];
+    let mut out = Vec::new();
+    for (variable, dir) in candidates {
+        let targets = dir.as_deref().map(target_roots).unwrap_or_default();
+        out.push((variable, None, dir));
+        out.extend(
+            targets
+                .into_iter()
+                .map(|(target, dir)| (variable, Some(target), Some(dir))),
+        );
+    }
+    out
+}
+
+//The roots in <root>/targets, named after their target, sorted.
+fn target_roots(root: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = fs::read_dir(root.join("targets")) else {
+        return Vec::new();
+    };
+    let mut roots: Vec<_> = entries
+        .flatten()
+        .filter(|x| x.path().is_dir())
+        .map(|x| (x.file_name().to_string_lossy().into_owned(), x.path()))
+        .collect();
+    roots.sort();
+    roots
+}
+
+pub fn find_install_roots(warnings: &mut Warnings) -> Vec<InstallRoot> {
+    let mut roots: Vec<InstallRoot> = Vec::new();
+    for (variable, target, dir) in install_dir_candidates()
+        .into_iter()
+        .filter_map(|(variable, target, dir)| Some((variable, target, dir?)))
+    {
+        //The same root reached through two variables would be listed twice.
+        if warnings.ok(&dir, fs::read_dir(&dir)).is_some()
+            && !roots.iter().any(|x| paths::same(&x.path, &dir))
+        {
+            roots.push(InstallRoot {
+                path: paths::normalize(&dir),
+                variable,
+                target,
+            });
+        }
+    }
+    roots
+}
+
+pub fn determine_pkgs_install_dir(warnings: &mut Warnings) -> Vec<PathBuf> {
+    find_install_roots(warnings)
+        .into_iter()
+        .map(|x| x.path)
+        .collect()
+}
+
+//Files that end up in bin directories but aren't binaries. Dotfiles (like a misplaced .crates.toml
+//or .DS_Store) are skipped as well.
+const NON_BINARIES: [&str; 2] = ["Thumbs.db", "desktop.ini"];
+
+pub fn list_pkgs(ir: &Path, all_files: bool, warnings: &mut Warnings) -> Option<Vec<String>> {
+    //Path to the Install Root Bin Directory
+    let ir_bin_path = ir.join("bin");
+
+    //If the dir doesn't exist return None.
+    let ir_bin = warnings.ok(&ir_bin_path, fs::read_dir(&ir_bin_path))?;
+
+    //Get binary names.
+    let mut names = Vec::new();
+    for entry in ir_bin {
+        let Some(entry) = warnings.ok(&ir_bin_path, entry) else {
+            continue;
+        };
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if !all_files && (name.starts_with('.') || NON_BINARIES.contains(&name.as_str())) {
+            continue;
+        }
+        //Follow symlinks, which cargo doesn't make but users do. When the file can't be checked
+        //it's listed, the warning explains why its details may be off.
+        let path = entry.path();
+        let program = match fs::metadata(&path) {
+            Ok(metadata) => paths::is_program(&metadata),
+            Err(e) => {
+                let missing = e.kind() == std::io::ErrorKind::NotFound;
+                warnings.push(&path, e);
+                !missing
+            }
+        };
+        if !all_files && !program {
+            warnings.not_program(&path);
+            continue;
+        }
+        names.push(name);
+    }
+
+    //This way it's easier to tell if the function failed.
+    if names.is_empty() {
+        None
+    } else {
+        Some(names)
+    }
+}
+
+//Directory the tool keeps its own files in.
+pub fn config_dir() -> Option<PathBuf> {
+    let base = env::var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|_| env::var("APPDATA").map(PathBuf::from))
+        .ok()
+        .or_else(|| Some(paths::home_dir()?.join(".config")))?;
+    Some(base.join("cargo-ls-crates"))
+}
+
+//Information about a package read from its Cargo.toml.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PkgInfo {
+    //Crate name, which can differ from the binary name.
+    pub name: String,
+    pub version: String,
+    pub description: String,
+    //Documentation URL, empty if the manifest has none.
+    pub documentation: String,
+    //Homepage URL, empty if the manifest has none.
+    pub homepage: String,
+    //Source repository URL, empty if the manifest has none.
+    pub repository: String,
+    pub categories: Vec<String>,
+    pub keywords: Vec<String>,
+    //Source directory in registry/src or git/checkouts, empty if the sources aren't there.
+    pub path: PathBuf,
+    //Where cargo recorded installing it from, like `registry+https://...`. Empty if not recorded.
+    pub source: String,
+    //Every version of the crate in registry/src, oldest first.
+    pub source_versions: Vec<String>,
+    //Features cargo recorded installing it with.
+    pub features: Vec<String>,
+    //Whether cargo recorded installing it with --no-default-features and --all-features.
+    pub no_default_features: bool,
+    pub all_features: bool,
+}
+
+//Read the details of a package from the Cargo.toml in dir. Fields it inherits are read from the
+//workspace root, which is between dir and workspace_root.
+fn read_manifest(
+    dir: &Path,
+    workspace_root: Option<&Path>,
+    name: &str,
+    version: &str,
+    warnings: &mut Warnings,
+) -> Option<PkgInfo> {
+    //Check if Cargo.toml exists
+    let cargo_toml_path = dir.join("Cargo.toml");
+    let mut cargo_toml = warnings.ok(&cargo_toml_path, fs::File::open(&cargo_toml_path))?;
+
+    //Read the Cargo.toml file
+    let mut cargo_toml_content = String::new();
+    warnings.ok(
+        &cargo_toml_path,
+        cargo_toml.read_to_string(&mut cargo_toml_content),
+    )?;
+
+    //Only look for the workspace root when needed, walking up the tree for every package is slow.
+    let workspace = workspace_root
+        .filter(|_| cargo_toml_content.contains("workspace"))
+        .and_then(|x| manifest::workspace_package_table(dir, x))
+        .unwrap_or_default();
+    //Fields inherited from the workspace are read from its table instead.
+    let table = |key| {
+        if manifest::inherits_from_workspace(&cargo_toml_content, key) {
+            &workspace
+        } else {
+            &cargo_toml_content
+        }
+    };
+    let string = |key| manifest::string(table(key), key).unwrap_or_default();
+
+    Some(PkgInfo {
+        name: name.to_string(),
+        version: version.to_string(),
+        //A missing or malformed description shouldn't hide the version. Descriptions can span
+        //multiple lines, but they're displayed on one.
+        description: manifest::normalize_whitespace(&string("description")),
+        documentation: string("documentation"),
+        homepage: string("homepage"),
+        repository: string("repository"),
+        categories: manifest::array(table("categories"), "categories"),
+        keywords: manifest::array(table("keywords"), "keywords"),
+        path: dir.to_path_buf(),
+        ..Default::default()
+    })
+}
+
+//Crates installed with `cargo install --git` are built in git/checkouts/<repo>-<hash>/<commit>/,
+//where the commit is shortened. The crate can be any package in the repository, and members of a
+//workspace can inherit fields from its root. Returns the checkout and the package directory in it.
+fn git_checkout(ir: &Path, name: &str, source: &str) -> Option<(PathBuf, PathBuf)> {
+    let (_, commit) = source.strip_prefix("git+")?.rsplit_once('#')?;
+    fs::read_dir(ir.join("git").join("checkouts"))
+        .ok()?
+        .flatten()
+        .filter_map(|x| fs::read_dir(x.path()).ok())
+        .flatten()
+        .flatten()
+        .filter(|x| {
+            x.file_name()
+                .to_str()
+                .is_some_and(|x| !x.is_empty() && commit.starts_with(x))
+        })
+        .find_map(|x| {
+            let checkout = x.path();
+            let dir = find_package(&checkout, name, 3)?;
+            Some((checkout, dir))
+        })
+}
+
+//Find the directory of the package called name, at most depth directories below dir.
+fn find_package(dir: &Path, name: &str, depth: usize) -> Option<PathBuf> {
+    let cargo_toml_content = fs::read_to_string(dir.join("Cargo.toml")).unwrap_or_default();
+    if manifest::table(&cargo_toml_content, "[package]")
+        .and_then(|x| manifest::string(x, "name"))
+        .is_some_and(|x| x == name)
+    {
+        return Some(dir.to_path_buf());
+    }
+    if depth == 0 {
+        return None;
+    }
+    //Build output and hidden directories like .git don't hold packages.
+    fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter(|x| x.file_type().is_ok_and(|x| x.is_dir()))
+        .filter(|x| x.file_name() != "target" && !x.file_name().to_string_lossy().starts_with('.'))
+        .find_map(|x| find_package(&x.path(), name, depth - 1))
+}
+
+//Get information (version, description, etc.) about installed cargo packages.
+//What's installed comes from cargo's records, the details from the sources in registry/src. Without
+//records every crate in registry/src counts as installed, including dependencies.
+pub fn get_pkgs_info(
+    ir: &Path,
+    progress: &mut Progress,
+    warnings: &mut Warnings,
+) -> Option<HashMap<String, PkgInfo>> {
+    let recorded = crates_toml::load(ir, warnings);
+
+    //Install Root Source Directory.
+    let ir_source = ir.join("registry").join("src");
+
+    //Check if it exists. Recorded crates are listed even without their sources.
+    let ir_source_dir = warnings.ok(&ir_source, fs::read_dir(&ir_source));
+
+    //Allocate an empty hashmap.
+    let mut map = HashMap::new();
+    //Every package found.
+    let mut found: Vec<PkgInfo> = Vec::new();
+    //Every version of each crate in registry/src.
+    let mut source_versions: HashMap<String, Vec<String>> = HashMap::new();
+
+    //Create regex expression used to separate the version and the pkg name.
+    //It's important to create the expression before the loop. Moving the creation here improved
+    //the performence 3x.
+    let re = regex::Regex::new(r"-\d{1,3}\.\d{1,3}\.\d{1,3}").unwrap();
+
+    //Count the packages first, so the progress bar knows how many there are.
+    if progress.is_enabled() {
+        let total = fs::read_dir(&ir_source)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|x| fs::read_dir(x.path()).ok())
+            .map(Iterator::count)
+            .sum();
+        progress.start(total);
+    }
+
+    //For each valid directory in the Install Root Source Directory find it's child directories and look
+    //for Cargo.toml files containing relevant package information.
+    for source_dir in ir_source_dir.into_iter().flatten() {
+        let Some(source_dir) = warnings.ok(&ir_source, source_dir) else {
+            continue;
+        };
+        let source_dir_path = source_dir.path();
+
+        //Stray files like .DS_Store aren't registries, symlinks to a shared cache may be.
+        if source_dir.file_type().is_ok_and(|x| x.is_file()) {
+            continue;
+        }
+
+        //A registry that can't be read is reported on its own, the others are still scanned.
+        let source_dir = match fs::read_dir(&source_dir_path) {
+            Ok(x) => x,
+            Err(e) => {
+                warnings.unreadable_registry(&source_dir_path, e);
+                continue;
+            }
+        };
+
+        for dir in source_dir {
+            progress.tick();
+            let Some(dir) = warnings.ok(&source_dir_path, dir) else {
+                continue;
+            };
+
+            //Get the package name from path.
+            let Some(pkg_name) = dir.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+
+            //separate the package version and name.
+            let Some(split_c) = re.find(pkg_name.as_str()) else {
+                continue;
+            };
+            let pkg_ver = &pkg_name[split_c.start() + 1..];
+            let pkg_name = &pkg_name[..split_c.start()];
+            source_versions
+                .entry(pkg_name.to_string())
+                .or_default()
+                .push(pkg_ver.to_string());
+
+            //Only read the manifests of installed crates when it's known which ones are.
+            if !recorded.is_empty()
+                && !recorded
+                    .iter()
+                    .any(|x| x.name == pkg_name && x.version == pkg_ver)
+            {
+                continue;
+            }
+
+            //Published manifests have inherited fields filled in by cargo, so there's no workspace
+            //to look for.
+            let Some(info) = read_manifest(&dir.path(), None, pkg_name, pkg_ver, warnings) else {
+                continue;
+            };
+            found.push(info);
+        }
+    }
+    progress.finish();
+
+    for versions in source_versions.values_mut() {
+        versions.sort_by(|a, b| version::compare(a, b));
+    }
+
+    if recorded.is_empty() {
+        //Several versions of a crate can be extracted, report the newest one. It's sorted last, so
+        //it's inserted last, replacing the others.
+        found.sort_by(|a, b| {
+            a.name
+                .cmp(&b.name)
+                .then(version::compare(&a.version, &b.version))
+        });
+    } else {
+        //Crates installed from git or a local path have no sources in registry/src, those from git
+        //are read from their checkout.
+        for x in &recorded {
+            let mut info = found
+                .iter()
+                .position(|y| y.name == x.name && y.version == x.version)
+                .map(|i| found.swap_remove(i))
+                .or_else(|| {
+                    let (checkout, dir) = git_checkout(ir, &x.name, &x.source)?;
+                    read_manifest(&dir, Some(&checkout), &x.name, &x.version, warnings)
+                })
+                .unwrap_or_else(|| PkgInfo {
+                    name: x.name.clone(),
+                    version: x.version.clone(),
+                    ..Default::default()
+                });
+            info.source = x.source.clone();
+            info.features = x.features.clone();
+            info.no_default_features = x.no_default_features;
+            info.all_features = x.all_features;
+            found.push(info);
+        }
+    }
+    for mut info in found {
+        info.source_versions = source_versions.get(&info.name).cloned().unwrap_or_default();
+        map.insert(info.name.clone(), info);
+    }
+
+    //Binaries named differently than their crate are looked up by their own name too.
+    for (bin, name) in aliases::resolve(&recorded, &aliases::load()) {
+        if let Some(info) = map.get(&name).filter(|_| !map.contains_key(&bin)) {
+            map.insert(bin, info.clone());
+        }
+    }
+
+    //If the hashmap is empty return None.
+    if map.is_empty() {
+        None
+    } else {
+        Some(map)
+    }
+}
+
+mod test {
+    #[test]
+    fn determine_pkgs_install_dir() {
+        use std::fs;
+        //Unique, so runs at the same time don't share it.
+        let tmp = std::env::temp_dir().join(format!(
+            "cargo-ls-crates-install-dirs-{}",
+            std::process::id()
+        ));
+        std::env::set_var("CARGO_INSTALL_ROOT", tmp.join("CIR_PATH"));
+        std::env::set_var("CARGO_HOME", tmp.join("CH_PATH"));
+        std::env::set_var("HOME", tmp.join("H_PATH"));
+        fs::create_dir_all(tmp.join("CIR_PATH")).unwrap();
+        fs::create_dir_all(tmp.join("CH_PATH/targets/x86_64-unknown-linux-musl")).unwrap();
+        fs::create_dir_all(tmp.join("H_PATH/.cargo")).unwrap();
+        let out = super::determine_pkgs_install_dir(&mut crate::warnings::Warnings::default());
+        assert_eq!(
+            out,
+            vec![
+                tmp.join("CIR_PATH"),
+                tmp.join("CH_PATH"),
+                tmp.join("CH_PATH/targets/x86_64-unknown-linux-musl"),
+                tmp.join("H_PATH").join(".cargo")
+            ]
+        );
+        let targets: Vec<_> = super::install_dir_candidates()
+            .into_iter()
+            .filter_map(|(variable, target, _)| Some((variable, target?)))
+            .collect();
+        assert_eq!(
+            targets,
+            vec![("CARGO_HOME", String::from("x86_64-unknown-linux-musl"))]
+        );
+        fs::remove_dir_all(&tmp).unwrap();
+    }
+
+    #[test]
+    fn list_pkgs() {
+        //Unique, so runs at the same time don't share it.
+        let root =
+            std::env::temp_dir().join(format!("cargo-ls-crates-list-pkgs-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("bin")).unwrap();
+        for name in ["rg", ".crates.toml", ".DS_Store", "Thumbs.db", "empty"] {
+            std::fs::write(root.join("bin").join(name), "").unwrap();
+        }
+        std::fs::write(root.join("bin/rg"), "\x7fELF").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let executable = std::fs::Permissions::from_mode(0o755);
+            std::fs::set_permissions(root.join("bin/rg"), executable.clone()).unwrap();
+            std::fs::set_permissions(root.join("bin/empty"), executable).unwrap();
+        }
+        let mut warnings = crate::warnings::Warnings::default();
+        assert_eq!(
+            super::list_pkgs(&root, false, &mut warnings),
+            Some(vec![String::from("rg")])
+        );
+        assert_eq!(warnings.not_programs(), [root.join("bin/empty")]);
+        let mut all =
+            super::list_pkgs(&root, true, &mut crate::warnings::Warnings::default()).unwrap();
+        all.sort();
+        assert_eq!(
+            all,
+            vec![".DS_Store", ".crates.toml", "Thumbs.db", "empty", "rg"]
+        );
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
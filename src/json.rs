@@ -0,0 +1,243 @@
+//Just enough JSON for machine-readable output and for reading the files cargo writes, without
+//pulling in serde.
+
+use std::{iter::Peekable, str::Chars};
+
+//A string literal, with quotes and control characters escaped.
+pub fn string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for x in s.chars() {
+        match x {
+            '"' => out += "\\\"",
+            '\\' => out += "\\\\",
+            '\n' => out += "\\n",
+            '\r' => out += "\\r",
+            '\t' => out += "\\t",
+            x if x.is_control() => out += &format!("\\u{:04x}", x as u32),
+            x => out.push(x),
+        }
+    }
+    out + "\""
+}
+
+//An array of string literals.
+pub fn strings<T: AsRef<str>>(items: &[T]) -> String {
+    let items: Vec<String> = items.iter().map(|x| string(x.as_ref())).collect();
+    format!("[{}]", items.join(", "))
+}
+
+//An object from keys and already written values. Keys are sorted, so the same data is always
+//written the same way whatever order it's collected in.
+pub fn object(mut members: Vec<(&str, String)>) -> String {
+    members.sort_by(|a, b| a.0.cmp(b.0));
+    let members: Vec<String> = members
+        .iter()
+        .map(|(key, value)| format!("{}: {value}", string(key)))
+        .collect();
+    format!("{{{}}}", members.join(", "))
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    //Kept as written, nothing here needs arithmetic.
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    //Members in the order they're written.
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(x) => x.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    //The strings in an array, other items are skipped.
+    pub fn strings(&self) -> Vec<String> {
+        match self {
+            Value::Array(x) => x
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+//Parse a whole document. None if it isn't valid JSON.
+pub fn parse(s: &str) -> Option<Value> {
+    let mut chars = s.chars().peekable();
+    let value = parse_value(&mut chars)?;
+    skip_whitespace(&mut chars);
+    chars.peek().is_none().then_some(value)
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.next_if(|x| x.is_ascii_whitespace()).is_some() {}
+}
+
+fn parse_value(chars: &mut Peekable<Chars>) -> Option<Value> {
+    skip_whitespace(chars);
+    match chars.peek()? {
+        '{' => {
+            chars.next();
+            let mut members = Vec::new();
+            skip_whitespace(chars);
+            if chars.next_if_eq(&'}').is_some() {
+                return Some(Value::Object(members));
+            }
+            loop {
+                skip_whitespace(chars);
+                chars.next_if_eq(&'"')?;
+                let key = parse_string(chars)?;
+                skip_whitespace(chars);
+                chars.next_if_eq(&':')?;
+                members.push((key, parse_value(chars)?));
+                skip_whitespace(chars);
+                match chars.next()? {
+                    ',' => continue,
+                    '}' => return Some(Value::Object(members)),
+                    _ => return None,
+                }
+            }
+        }
+        '[' => {
+            chars.next();
+            let mut items = Vec::new();
+            skip_whitespace(chars);
+            if chars.next_if_eq(&']').is_some() {
+                return Some(Value::Array(items));
+            }
+            loop {
+                items.push(parse_value(chars)?);
+                skip_whitespace(chars);
+                match chars.next()? {
+                    ',' => continue,
+                    ']' => return Some(Value::Array(items)),
+                    _ => return None,
+                }
+            }
+        }
+        '"' => {
+            chars.next();
+            parse_string(chars).map(Value::String)
+        }
+        _ => {
+            let mut word = String::new();
+            while let Some(x) = chars.next_if(|x| x.is_ascii_alphanumeric() || "+-.".contains(*x)) {
+                word.push(x);
+            }
+            match word.as_str() {
+                "null" => Some(Value::Null),
+                "true" => Some(Value::Bool(true)),
+                "false" => Some(Value::Bool(false)),
+                x if x.parse::<f64>().is_ok() => Some(Value::Number(word)),
+                _ => None,
+            }
+        }
+    }
+}
+
+//Read a string after its opening quote, up to and including the closing one.
+fn parse_string(chars: &mut Peekable<Chars>) -> Option<String> {
+    let mut out = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(out),
+            '\\' => match chars.next()? {
+                'n' => out.push('\n'),
+                't' => out.push('\t'),
+                'r' => out.push('\r'),
+                'b' => out.push('\u{8}'),
+                'f' => out.push('\u{c}'),
+                'u' => {
+                    let code = hex4(chars)?;
+                    //Characters outside the BMP are written as a surrogate pair.
+                    let code = if (0xd800..0xdc00).contains(&code) {
+                        chars.next_if_eq(&'\\')?;
+                        chars.next_if_eq(&'u')?;
+                        let low = hex4(chars)?;
+                        0x10000 + ((code - 0xd800) << 10) + low.checked_sub(0xdc00)?
+                    } else {
+                        code
+                    };
+                    out.push(char::from_u32(code)?);
+                }
+                x => out.push(x),
+            },
+            x => out.push(x),
+        }
+    }
+}
+
+fn hex4(chars: &mut Peekable<Chars>) -> Option<u32> {
+    let digits: String = (0..4).filter_map(|_| chars.next()).collect();
+    if digits.len() != 4 {
+        return None;
+    }
+    u32::from_str_radix(&digits, 16).ok()
+}
+
+mod test {
+    #[test]
+    fn string() {
+        assert_eq!(super::string("rg"), "\"rg\"");
+        assert_eq!(
+            super::string("a \"quoted\"\\path\n\u{1}"),
+            "\"a \\\"quoted\\\"\\\\path\\n\\u0001\""
+        );
+        assert_eq!(super::strings(&["a", "b"]), "[\"a\", \"b\"]");
+        assert_eq!(super::strings::<&str>(&[]), "[]");
+    }
+
+    #[test]
+    fn object() {
+        assert_eq!(
+            super::object(vec![
+                ("name", super::string("rg")),
+                ("bins", String::from("[]"))
+            ]),
+            "{\"bins\": [], \"name\": \"rg\"}"
+        );
+        assert_eq!(super::object(Vec::new()), "{}");
+    }
+
+    #[test]
+    fn parse() {
+        use super::Value;
+        let value = super::parse(
+            r#"{"installs": {"rg 13.0.0": {"bins": ["rg"], "all_features": false, "rustc": null, "n": -1.5e3}}, "s": "a\"\u00e9\ud83e\udd80\n"}"#,
+        )
+        .unwrap();
+        let rg = value.get("installs").unwrap().get("rg 13.0.0").unwrap();
+        assert_eq!(rg.get("bins").unwrap().strings(), ["rg"]);
+        assert_eq!(rg.get("all_features"), Some(&Value::Bool(false)));
+        assert_eq!(rg.get("rustc"), Some(&Value::Null));
+        assert_eq!(rg.get("n"), Some(&Value::Number(String::from("-1.5e3"))));
+        assert_eq!(value.get("s").unwrap().as_str(), Some("a\"é🦀\n"));
+        assert_eq!(super::parse(" [] "), Some(Value::Array(Vec::new())));
+
+        for broken in ["", "{", "[1,]", "{\"a\" 1}", "\"open", "[1] 2", "nul"] {
+            assert_eq!(super::parse(broken), None, "{broken}");
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let s = "quote \" backslash \\ tab \t control \u{1} 🦀";
+        assert_eq!(super::parse(&super::string(s)).unwrap().as_str(), Some(s));
+    }
+}
@@ -0,0 +1,164 @@
+//Scanning cargo install roots for what's installed in them, the library behind the command line
+//tool for other tools to embed.
+
+//The modules are shared with the binary, which declares them as well and uses what scanning
+//doesn't. Only what's below is the library's API.
+#[allow(dead_code)]
+mod aliases;
+#[allow(dead_code)]
+mod config;
+#[allow(dead_code)]
+mod crates_toml;
+#[allow(dead_code)]
+mod dates;
+#[allow(dead_code)]
+mod i18n;
+#[allow(dead_code)]
+mod installed;
+#[allow(dead_code)]
+mod json;
+#[allow(dead_code)]
+mod locale;
+#[allow(dead_code)]
+mod manifest;
+#[allow(dead_code)]
+mod package;
+#[allow(dead_code)]
+mod paths;
+#[allow(dead_code)]
+mod progress;
+#[allow(dead_code)]
+mod version;
+#[allow(dead_code)]
+mod warnings;
+
+use installed::{config_dir, PkgInfo};
+use progress::Progress;
+use std::io;
+use warnings::Warnings;
+
+pub use installed::InstallRoot;
+
+/// Install roots in the order cargo looks for them. Unreadable ones are skipped.
+pub fn install_roots() -> Vec<InstallRoot> {
+    installed::find_install_roots(&mut Warnings::default())
+}
+
+/// A crate installed in an install root, with every binary it installed there.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InstalledPackage {
+    /// Crate name, or the binary name for binaries without metadata.
+    pub name: String,
+    /// None for binaries without metadata, like scripts copied in by hand.
+    pub version: Option<String>,
+    /// None when the sources aren't in `registry/src` or have no description.
+    pub description: Option<String>,
+    /// Where cargo recorded installing it from, like `registry+https://...`.
+    pub source: Option<String>,
+    /// Features cargo recorded installing it with.
+    pub features: Vec<String>,
+    /// File names in the root's `bin` directory, sorted.
+    pub binaries: Vec<String>,
+    /// Install root it's in.
+    pub root: InstallRoot,
+}
+
+//What's installed in one root. Unreadable paths are skipped.
+fn scan_root(root: &InstallRoot, warnings: &mut Warnings) -> Vec<InstalledPackage> {
+    let bins = installed::list_pkgs(&root.path, false, warnings).unwrap_or_default();
+    let map =
+        installed::get_pkgs_info(&root.path, &mut Progress::new("manifests", false), warnings)
+            .unwrap_or_default();
+    let installed = bins.iter().map(|x| {
+        let bin = paths::bin_name(x);
+        (bin, map.get(bin), root.path.clone())
+    });
+    package::packages(installed)
+        .into_iter()
+        .map(|x| {
+            let info = map.get(&x.binaries[0]);
+            let source = info.map(|x| x.source.clone()).filter(|x| !x.is_empty());
+            InstalledPackage {
+                name: x.name,
+                version: x.version,
+                description: x.description,
+                source,
+                features: info.map(|x| x.features.clone()).unwrap_or_default(),
+                binaries: x.binaries,
+                root: root.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Every crate installed in every install root, sorted by name and root. Fails when there's no
+/// install root. Unreadable paths are skipped, so what's in them is missing.
+pub fn scan() -> io::Result<Vec<InstalledPackage>> {
+    let mut warnings = Warnings::default();
+    let roots = installed::find_install_roots(&mut warnings);
+    if roots.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "no install root found",
+        ));
+    }
+    let mut packages: Vec<InstalledPackage> = roots
+        .iter()
+        .flat_map(|x| scan_root(x, &mut warnings))
+        .collect();
+    packages.sort_by(|a, b| (&a.name, &a.root.path).cmp(&(&b.name, &b.root.path)));
+    Ok(packages)
+}
+
+mod test {
+    #[test]
+    fn scan_root() {
+        //Unique, so runs at the same time don't share it.
+        let root =
+            std::env::temp_dir().join(format!("cargo-ls-crates-scan-root-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("bin")).unwrap();
+        for name in ["rg", "script"] {
+            std::fs::write(root.join("bin").join(name), "\x7fELF").unwrap();
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let executable = std::fs::Permissions::from_mode(0o755);
+                std::fs::set_permissions(root.join("bin").join(name), executable).unwrap();
+            }
+        }
+        std::fs::write(
+            root.join(".crates2.json"),
+            r#"{"installs":{"ripgrep 13.0.0 (registry+https://github.com/rust-lang/crates.io-index)":{"bins":["rg"],"features":["pcre2"]}}}"#,
+        )
+        .unwrap();
+        let install_root = crate::InstallRoot {
+            path: root.clone(),
+            variable: "CARGO_HOME",
+            target: None,
+        };
+        let packages = crate::scan_root(&install_root, &mut crate::Warnings::default());
+        assert_eq!(
+            packages,
+            [
+                crate::InstalledPackage {
+                    name: String::from("ripgrep"),
+                    version: Some(String::from("13.0.0")),
+                    source: Some(String::from(
+                        "registry+https://github.com/rust-lang/crates.io-index"
+                    )),
+                    features: vec![String::from("pcre2")],
+                    binaries: vec![String::from("rg")],
+                    root: install_root.clone(),
+                    description: None,
+                },
+                crate::InstalledPackage {
+                    name: String::from("script"),
+                    binaries: vec![String::from("script")],
+                    root: install_root.clone(),
+                    ..Default::default()
+                },
+            ]
+        );
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,156 @@
+//Dates and numbers written the way the user's locale writes them. The C locale, which scripts and
+//CI usually run with, locales without a known convention and --iso all get the same forms:
+//2024-01-01 and 12345.
+
+use crate::dates;
+use std::{env, time::SystemTime};
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+//The locale set for a category like LC_TIME, in the order every other program reads them: LC_ALL
+//overrides the category, which overrides LANG. Empty variables count as unset.
+fn name_from(category: &str, var: impl Fn(&str) -> Option<String>) -> Option<String> {
+    ["LC_ALL", category, "LANG"]
+        .into_iter()
+        .filter_map(var)
+        .find(|x| !x.is_empty())
+}
+
+pub fn name(category: &str) -> Option<String> {
+    name_from(category, |x| env::var(x).ok())
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Style {
+    #[default]
+    Iso,
+    //en_US: Jan 1, 2024 and 12,345.
+    Us,
+    //Other English locales: 1 Jan 2024 and 12,345.
+    En,
+    //1.01.2024 and 12 345.
+    Pl,
+}
+
+impl Style {
+    pub fn from_locale(locale: &str) -> Style {
+        let name = locale.split(['.', '@']).next().unwrap_or_default();
+        let mut parts = name.split(['_', '-']);
+        match (parts.next(), parts.next()) {
+            (Some("en"), Some("US")) => Style::Us,
+            (Some("en"), _) => Style::En,
+            (Some("pl"), _) => Style::Pl,
+            _ => Style::Iso,
+        }
+    }
+
+    pub fn detect() -> Style {
+        name("LC_TIME")
+            .map(|x| Style::from_locale(&x))
+            .unwrap_or_default()
+    }
+
+    pub fn date(self, time: SystemTime) -> String {
+        let (year, month, day) = dates::civil(time);
+        match self {
+            Style::Iso => dates::ymd(time),
+            Style::Us => format!("{} {day}, {year}", MONTHS[month as usize - 1]),
+            Style::En => format!("{day} {} {year}", MONTHS[month as usize - 1]),
+            Style::Pl => format!("{day}.{month:02}.{year}"),
+        }
+    }
+
+    //A number with one decimal place, like 4.2, or 4,2 in Polish.
+    pub fn decimal(self, x: f64) -> String {
+        let out = format!("{x:.1}");
+        match self {
+            Style::Pl => out.replace('.', ","),
+            _ => out,
+        }
+    }
+
+    pub fn count(self, n: usize) -> String {
+        let separator = match self {
+            Style::Iso => return n.to_string(),
+            Style::Us | Style::En => ',',
+            //Polish only groups numbers of five digits or more, with a non-breaking space.
+            Style::Pl if n < 10000 => return n.to_string(),
+            Style::Pl => '\u{a0}',
+        };
+        let digits = n.to_string();
+        let mut out = String::new();
+        for (i, x) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                out.push(separator);
+            }
+            out.push(x);
+        }
+        out
+    }
+}
+
+mod test {
+    #[test]
+    fn name_from() {
+        let vars = |set: &'static [(&'static str, &'static str)]| {
+            move |x: &str| {
+                set.iter()
+                    .find(|(name, _)| *name == x)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        let name = |set| super::name_from("LC_TIME", vars(set));
+        assert_eq!(
+            name(&[("LANG", "en_US.UTF-8")]).as_deref(),
+            Some("en_US.UTF-8")
+        );
+        assert_eq!(
+            name(&[("LANG", "en_US.UTF-8"), ("LC_TIME", "pl_PL.UTF-8")]).as_deref(),
+            Some("pl_PL.UTF-8")
+        );
+        assert_eq!(
+            name(&[
+                ("LC_ALL", "pl_PL.UTF-8"),
+                ("LC_TIME", "en_GB"),
+                ("LANG", "C")
+            ])
+            .as_deref(),
+            Some("pl_PL.UTF-8")
+        );
+        assert_eq!(name(&[("LC_ALL", ""), ("LANG", "C")]).as_deref(), Some("C"));
+        assert_eq!(name(&[]), None);
+    }
+
+    #[test]
+    fn from_locale() {
+        use super::Style;
+        assert_eq!(Style::from_locale("en_US.UTF-8"), Style::Us);
+        assert_eq!(Style::from_locale("en_GB.UTF-8"), Style::En);
+        assert_eq!(Style::from_locale("pl_PL.UTF-8"), Style::Pl);
+        assert_eq!(Style::from_locale("C"), Style::Iso);
+        assert_eq!(Style::from_locale("de_DE.UTF-8"), Style::Iso);
+    }
+
+    #[test]
+    fn format() {
+        use super::Style;
+        use std::time::{Duration, UNIX_EPOCH};
+        let day = UNIX_EPOCH + Duration::from_secs(19723 * 86400);
+        assert_eq!(Style::Iso.date(day), "2024-01-01");
+        assert_eq!(Style::Us.date(day), "Jan 1, 2024");
+        assert_eq!(Style::En.date(day), "1 Jan 2024");
+        assert_eq!(Style::Pl.date(day), "1.01.2024");
+
+        assert_eq!(Style::Iso.count(1234567), "1234567");
+        assert_eq!(Style::Us.count(1234567), "1,234,567");
+        assert_eq!(Style::En.count(123), "123");
+        assert_eq!(Style::Pl.count(1234), "1234");
+        assert_eq!(Style::Pl.count(12345), "12\u{a0}345");
+
+        assert_eq!(Style::Iso.decimal(4.24), "4.2");
+        assert_eq!(Style::Us.decimal(1.0), "1.0");
+        assert_eq!(Style::Pl.decimal(4.25), "4,2");
+    }
+}
@@ -0,0 +1,46 @@
+//Crates to update without --locked, kept as one crate name per line in the tool's config directory.
+//cargo doesn't record whether a crate was installed with --locked, so the choice made with
+//`update --no-locked` is remembered for later updates, until `update --locked` is used.
+
+use std::{fs, io, path::PathBuf};
+
+pub fn path() -> Option<PathBuf> {
+    Some(crate::config_dir()?.join("unlocked"))
+}
+
+//Missing or unreadable file means every crate is updated with --locked.
+pub fn unlocked() -> Vec<String> {
+    let Some(path) = path() else {
+        return Vec::new();
+    };
+    fs::read_to_string(path)
+        .map(|x| {
+            x.lines()
+                .map(str::trim)
+                .filter(|x| !x.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+//Remember whether the crates are updated with --locked.
+pub fn set(names: &[&str], locked: bool) -> io::Result<()> {
+    let Some(path) = path() else {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "failed to locate the config directory",
+        ));
+    };
+    let mut unlocked = unlocked();
+    unlocked.retain(|x| !names.contains(&x.as_str()));
+    if !locked {
+        unlocked.extend(names.iter().map(|x| x.to_string()));
+        unlocked.sort();
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, unlocked.join("\n") + "\n")
+}
@@ -10,137 +10,169 @@
 //
 //source: https://doc.rust-lang.org/cargo/commands/cargo-install.html
 
-use colored::Colorize;
-use std::{collections::HashMap, env, fs, io::Read, process::exit};
+mod aliases;
+mod build_info;
+mod cheatsheet;
+mod check;
+mod checksum;
+mod commands;
+mod config;
+mod crash;
+mod crates_toml;
+mod dates;
+mod deps;
+mod export;
+mod glob;
+mod groups;
+mod i18n;
+mod installed;
+mod json;
+mod locale;
+mod locked;
+mod man;
+mod manifest;
+mod network;
+mod package;
+mod paths;
+mod progress;
+mod stars;
+mod suggest;
+mod system;
+mod term;
+mod toolchains;
+mod uninstall;
+mod update;
+mod version;
+mod warnings;
 
-fn determine_pkgs_install_dir() -> Vec<String> {
-    //According to cargo documentation it's best to start looking for the Install Root Directory in
-    //this order:
-    let mut dirs = vec![
-        env::var("CARGO_INSTALL_ROOT"),
-        env::var("CARGO_HOME"),
-        env::var("HOME").map(|x| x + "/.cargo"),
-    ];
-    dirs.dedup();
-    dirs.iter()
-        .flatten()
-        .filter_map(|x| {
-            if fs::read_dir(x).is_ok() {
-                Some(x.clone())
-            } else {
-                None
+use colored::Colorize;
+use commands::{Command, Context};
+use i18n::{tr, Lang};
+use installed::{
+    config_dir, determine_pkgs_install_dir, get_pkgs_info, install_dir_candidates, list_pkgs,
+    PkgInfo,
+};
+use locale::Style;
+use progress::Progress;
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+    path::PathBuf,
+    process::exit,
+    time::{Duration, Instant, UNIX_EPOCH},
+};
+use warnings::Warnings;
+
+//Exit codes, so wrapper scripts can tell failures apart. Documented in the README.
+const EXIT_ERROR: i32 = 1;
+const EXIT_NO_ROOT: i32 = 2;
+const EXIT_NOTHING_INSTALLED: i32 = 3;
+const EXIT_POLICY_VIOLATION: i32 = 4;
+const EXIT_NETWORK: i32 = 5;
+
+//Get the installed crates and their versions, and the binaries without metadata.
+fn installed_crates(
+    pkgs: &[String],
+    map: &HashMap<String, PkgInfo>,
+) -> (BTreeMap<String, String>, Vec<String>) {
+    let mut crates = BTreeMap::new();
+    let mut unknown = Vec::new();
+    for pkg in pkgs {
+        let pkg = paths::bin_name(pkg);
+        match map.get(pkg).filter(|x| !crates_toml::is_local(&x.source)) {
+            Some(info) => {
+                crates.insert(info.name.clone(), info.version.clone());
             }
-        })
-        .collect()
+            None => unknown.push(pkg.to_string()),
+        }
+    }
+    //Sorted without duplicates, so exports stay the same whatever order the roots are scanned in.
+    unknown.sort();
+    unknown.dedup();
+    (crates, unknown)
 }
 
-fn list_pkgs(ir: &str) -> Option<Vec<String>> {
-    //Path to the Install Root Bin Directory
-    let ir_bin = ir.to_owned() + "/bin";
-
-    //If the dir doesn't exist return None.
-    let Ok(ir_bin) = fs::read_dir(ir_bin) else{
-        return None;
-    };
-
-    //Get binary names.
-    let names: Vec<_> = ir_bin
-        .filter_map(|x| x.ok()?.file_name().to_str().map(str::to_string))
-        .collect();
-
-    //This way it's easier to tell if the function failed.
-    if names.is_empty() {
-        None
-    } else {
-        Some(names)
+//Exit because a crate argument isn't installed, suggesting installed names it may be a typo of.
+fn exit_not_installed<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+    lang: Lang,
+) -> ! {
+    eprintln!("{}: {name}", tr(lang, "err-not-installed"));
+    let close = suggest::closest(name, candidates);
+    if !close.is_empty() {
+        eprintln!("{} {}?", tr(lang, "msg-did-you-mean"), close.join(", "));
     }
+    exit(EXIT_ERROR);
 }
 
-//Get information (version and description) about installed cargo packages.
-fn get_pkgs_info(ir: &str) -> Option<HashMap<String, (String, String)>> {
-    //Install Root Source Directory.
-    let ir_source = ir.to_owned() + "/registry/src";
-
-    //Check if it exists.
-    let Ok(ir_source) = fs::read_dir(&ir_source) else{
-        return None;
-    };
-
-    //Allocate an empty hashmap.
-    let mut map = HashMap::new();
-
-    //Create regex expression used to separate the version and the pkg name.
-    //It's important to create the expression before the loop. Moving the creation here improved
-    //the performence 3x.
-    let re = regex::Regex::new(r"-\d{1,3}\.\d{1,3}\.\d{1,3}").unwrap();
-
-    //For each valid directory in the Install Root Source Directory find it's child directories and look
-    //for Cargo.toml files containing relevant package information.
-    for source_dir in ir_source.into_iter().flatten() {
-        //Check if dir exists.
-        let Ok(source_dir) = fs::read_dir(source_dir.path()) else {continue;};
-
-        for dir in source_dir.into_iter().flatten() {
-            //Get the package name from path.
-            let Some(pkg_name) = dir.file_name().to_str().map(str::to_string) else {continue;};
-
-            //Check if Cargo.toml exists
-            let Ok(mut cargo_toml) =
-                fs::File::open(dir.path().to_str()?.to_string() + "/Cargo.toml")
-            else {continue;};
-
-            //Read the Cargo.toml file
-            let mut cargo_toml_content = String::new();
-            let Ok(_) = cargo_toml.read_to_string(&mut cargo_toml_content) else {continue;};
-
-            //Get the package description
-            let Some(start) = cargo_toml_content.find("description = \"") else {continue;};
-            let Some(end) = cargo_toml_content[start..].find('\n') else {continue;};
-            let decs = &cargo_toml_content[start..start + end];
-            let Some(start) = decs.find('\"') else { continue;};
-            let Some(end) = decs.rfind('\"') else {continue;};
-            let desc = &decs[start + 1..end];
-
-            //separate the package version and name.
-            let Some(split_c) = re.find(pkg_name.as_str()) else{continue;};
-            let pkg_ver = &pkg_name[split_c.start() + 1..];
-            let pkg_name = &pkg_name[..split_c.start()];
-
-            //Insert them into the hashmap.
-            map.insert(
-                pkg_name.to_string(),
-                (pkg_ver.to_string(), desc.to_string()),
-            );
-
-            //Find alternative names for the package.
-            let find_start = "[[bin]]\nname = \"";
-            // Find alt name for package
-            let Some(start) = cargo_toml_content.find(find_start) else {continue;};
-            let Some(end) = cargo_toml_content[start+find_start.len()..].find('\n') else {continue;};
-
-            let alt_pkg_name =
-                &cargo_toml_content[start + find_start.len()..start + find_start.len() + end - 1];
-
-            //Insert them into the hashmap.
-            map.insert(
-                alt_pkg_name.to_string(),
-                (pkg_ver.to_string(), desc.to_string()),
-            );
-        }
-    }
-    //If the hashmap is empty return None.
-    if map.is_empty() {
-        None
-    } else {
-        Some(map)
-    }
+//Find the binary in the first install root that has it.
+fn bin_path(install_dirs: &[PathBuf], name: &str) -> Option<PathBuf> {
+    install_dirs
+        .iter()
+        .map(|x| x.join("bin").join(name))
+        .find(|x| x.is_file())
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Default)]
 struct CliOptions {
+    command: Command,
     print_versions: bool,
     print_descs: bool,
     print_paths: bool,
+    timing: bool,
+    help: bool,
+    version: bool,
+    //Print machine-readable JSON (--json or --output json).
+    json: bool,
+    plain: bool,
+    //Only list starred crates.
+    starred: bool,
+    //Export as a Dockerfile.
+    dockerfile: bool,
+    //Export as a devcontainer.json.
+    devcontainer: bool,
+    //Toolbox manifest to check installed crates against, a file or an HTTPS URL.
+    against: Option<String>,
+    //SHA-256 the manifest has to have.
+    sha256: Option<String>,
+    //Don't skip dotfiles and other non-binaries in bin directories.
+    all_files: bool,
+    //Show the compiler version and build date of each binary.
+    build_info: bool,
+    no_progress: bool,
+    //List every path that couldn't be read, not only how many there were.
+    verbose: bool,
+    //Compare with versions installed through the system package manager.
+    compare_system: bool,
+    //Only print what uninstall would do.
+    dry_run: bool,
+    //Uninstall without asking first.
+    yes: bool,
+    //Update with --locked (true) or without (false), remembered for the crates updated.
+    locked: Option<bool>,
+    //Target triple update builds for, into a root of its own.
+    target: Option<String>,
+    //Show the documentation URL of each crate.
+    docs: bool,
+    //Show the homepage and repository URLs under each crate.
+    wide: bool,
+    //Render the cheatsheet as HTML instead of markdown.
+    html: bool,
+    //List crates under headings inferred from their categories.
+    by_category: bool,
+    //Also show versions in registry/src other than the installed one.
+    all_source_versions: bool,
+    //List crates by crate name instead of binary name.
+    crate_names: bool,
+    //Write dates and numbers the same way in every locale.
+    iso: bool,
+    //Show exact dates even when printing to a terminal.
+    absolute_time: bool,
+    //Line length to fit the listing into, overrides COLUMNS.
+    width: Option<usize>,
+    //Overrides the language detected from LANG.
+    lang: Option<Lang>,
 }
 
 impl CliOptions {
@@ -154,35 +186,176 @@ impl CliOptions {
     }
 }
 
-fn print_help() -> ! {
-    println!("Usage:");
-    let options = "OPTIONS".yellow().bold();
+fn print_help(lang: Lang) -> ! {
+    println!("{}", tr(lang, "help-usage"));
+    let options = tr(lang, "help-options").yellow().bold();
     let call = format!("{} {}", "cargo".red(), "ls-crates".blue().bold());
     println!("{call} [{options}]");
+    println!("{call} list [{options}] - {}", tr(lang, "help-list"));
+    println!("{call} doctor - {}", tr(lang, "help-debug-info"));
+    println!(
+        "{call} cheatsheet [--html] - {}",
+        tr(lang, "help-cheatsheet")
+    );
+    println!(
+        "{call} check --against <file|url> [--sha256 <hex>] - {}",
+        tr(lang, "help-check")
+    );
+    println!("{call} deps <crate> - {}", tr(lang, "help-deps"));
+    println!(
+        "{call} deps --diff <a> <b> - {}",
+        tr(lang, "help-deps-diff")
+    );
+    println!(
+        "{call} export --dockerfile - {}",
+        tr(lang, "help-export-dockerfile")
+    );
+    println!(
+        "{call} export --devcontainer - {}",
+        tr(lang, "help-export-devcontainer")
+    );
+    println!("{call} groups - {}", tr(lang, "help-groups"));
+    println!("{call} info <crate> - {}", tr(lang, "help-info"));
+    println!("{call} man <crate> - {}", tr(lang, "help-man"));
+    println!("{call} outdated - {}", tr(lang, "help-outdated"));
+    println!("{call} star <crate> - {}", tr(lang, "help-star"));
+    println!("{call} unstar <crate> - {}", tr(lang, "help-unstar"));
+    println!(
+        "{call} uninstall [--dry-run] [--yes] <crate|pattern>... - {}",
+        tr(lang, "help-uninstall")
+    );
+    println!(
+        "{call} update [crate|pattern...] - {}",
+        tr(lang, "help-update")
+    );
+    println!(
+        "{call} update --target <triple> <crate|pattern>... - {}",
+        tr(lang, "help-update-target")
+    );
     println!("{options}:");
-    println!("\t-h --help print help");
-    println!("\t-v print versions");
-    println!("\t-d print descriptions");
-    println!("{}:", "Examples".purple());
-    println!("{call} -v - print package names and versions");
-    println!("{call} -d - print package names and descriptions");
-    println!("{call} -vd - print package names, descriptions and versions");
-    println!("{call} -dv - print package names, descriptions and versions");
-    println!("Note:\nInvalid arguments will be ignored.");
+    println!("\t-h --help {}", tr(lang, "help-help"));
+    println!("\t--version {}", tr(lang, "help-version"));
+    println!("\t--json --output json {}", tr(lang, "help-json"));
+    println!("\t-v {}", tr(lang, "help-versions"));
+    println!("\t-d {}", tr(lang, "help-descs"));
+    println!("\t--timing {}", tr(lang, "help-timing"));
+    println!("\t--plain {}", tr(lang, "help-plain"));
+    println!("\t--starred {}", tr(lang, "help-starred"));
+    println!("\t--by-category {}", tr(lang, "help-by-category"));
+    println!("\t--build-info {}", tr(lang, "help-build-info"));
+    println!("\t--docs {}", tr(lang, "help-docs"));
+    println!("\t--wide {}", tr(lang, "help-wide"));
+    println!(
+        "\t--all-source-versions {}",
+        tr(lang, "help-all-source-versions")
+    );
+    println!("\t--bin-names {}", tr(lang, "help-bin-names"));
+    println!("\t--crate-names {}", tr(lang, "help-crate-names"));
+    println!("\t--all-files {}", tr(lang, "help-all-files"));
+    println!("\t--no-progress {}", tr(lang, "help-no-progress"));
+    println!("\t--verbose {}", tr(lang, "help-verbose"));
+    println!("\t--compare-system {}", tr(lang, "help-compare-system"));
+    println!("\t--locked --no-locked {}", tr(lang, "help-locked"));
+    println!("\t--iso {}", tr(lang, "help-iso"));
+    println!("\t--absolute-time {}", tr(lang, "help-absolute-time"));
+    println!("\t--width <columns> {}", tr(lang, "help-width"));
+    println!("\t--lang <en|pl> {}", tr(lang, "help-lang"));
+    println!("{}:", tr(lang, "help-examples").purple());
+    println!("{call} -v - {}", tr(lang, "help-example-v"));
+    println!("{call} -d - {}", tr(lang, "help-example-d"));
+    println!("{call} -vd - {}", tr(lang, "help-example-vd"));
+    println!("{call} -dv - {}", tr(lang, "help-example-vd"));
+    println!("{}", tr(lang, "help-exit-codes"));
+    println!("{}", tr(lang, "help-note"));
     std::process::exit(0)
 }
 
 //Accept an argument rather than get the cli args from the api to enable testing.
 fn parse_args<T: ToString>(args: &[T]) -> CliOptions {
-    let mut op = CliOptions {
-        print_descs: false,
-        print_versions: false,
-        print_paths: false,
-    };
-    for arg in args.into_iter().skip(1) {
-        let arg = arg.to_string();
+    let mut op = CliOptions::default();
+    let mut args = args.iter().skip(1).map(ToString::to_string);
+    while let Some(arg) = args.next() {
         if arg == "-h" || arg == "--help" {
-            print_help();
+            op.help = true;
+            continue;
+        }
+        //Long options are matched as a whole, so their letters don't toggle the short flags.
+        if arg.starts_with("--") {
+            match arg.split_once('=') {
+                Some(("--lang", value)) => op.lang = Lang::from_locale(value),
+                _ if arg == "--lang" => op.lang = args.next().and_then(|x| Lang::from_locale(&x)),
+                Some(("--width", value)) => op.width = value.parse().ok(),
+                _ if arg == "--width" => op.width = args.next().and_then(|x| x.parse().ok()),
+                Some(("--output", value)) => op.json = value == "json",
+                _ if arg == "--output" => op.json = args.next().is_some_and(|x| x == "json"),
+                _ if arg == "--version" => op.version = true,
+                _ if arg == "--json" => op.json = true,
+                _ if arg == "--iso" => op.iso = true,
+                _ if arg == "--absolute-time" => op.absolute_time = true,
+                _ if arg == "--against" => op.against = args.next(),
+                _ if arg == "--sha256" => op.sha256 = args.next(),
+                _ if arg == "--dry-run" => op.dry_run = true,
+                _ if arg == "--yes" => op.yes = true,
+                _ if arg == "--target" => op.target = args.next(),
+                _ if arg == "--locked" => op.locked = Some(true),
+                _ if arg == "--no-locked" => op.locked = Some(false),
+                _ if arg == "--timing" => op.timing = true,
+                _ if arg == "--plain" => op.plain = true,
+                _ if arg == "--starred" => op.starred = true,
+                _ if arg == "--all-files" => op.all_files = true,
+                _ if arg == "--build-info" => op.build_info = true,
+                _ if arg == "--no-progress" => op.no_progress = true,
+                _ if arg == "--verbose" => op.verbose = true,
+                _ if arg == "--compare-system" => op.compare_system = true,
+                //From before outdated was a subcommand.
+                _ if arg == "--outdated" => op.command = Command::Outdated,
+                _ if arg == "--docs" => op.docs = true,
+                _ if arg == "--wide" => op.wide = true,
+                _ if arg == "--html" => op.html = true,
+                _ if arg == "--by-category" => op.by_category = true,
+                _ if arg == "--all-source-versions" => op.all_source_versions = true,
+                _ if arg == "--bin-names" => op.crate_names = false,
+                _ if arg == "--crate-names" => op.crate_names = true,
+                _ if arg == "--dockerfile" => op.dockerfile = true,
+                _ if arg == "--devcontainer" => op.devcontainer = true,
+                _ => {}
+            }
+            continue;
+        }
+        if let Some(command) = Command::from_name(&arg) {
+            op.command = match command {
+                Command::Deps(_) => match args.next() {
+                    Some(x) if x == "--diff" => match args.next().zip(args.next()) {
+                        Some((a, b)) => Command::DepsDiff(a, b),
+                        None => continue,
+                    },
+                    Some(x) => Command::Deps(x),
+                    None => continue,
+                },
+                Command::Info(_) => match args.next() {
+                    Some(x) => Command::Info(x),
+                    None => continue,
+                },
+                Command::Man(_) => match args.next() {
+                    Some(x) => Command::Man(x),
+                    None => continue,
+                },
+                Command::Star(_) => match args.next() {
+                    Some(x) => Command::Star(x),
+                    None => continue,
+                },
+                Command::Unstar(_) => match args.next() {
+                    Some(x) => Command::Unstar(x),
+                    None => continue,
+                },
+                x => x,
+            };
+            continue;
+        }
+        //Everything after update or uninstall that isn't an option is a crate to act on.
+        if let Command::Update(names) | Command::Uninstall(names) = &mut op.command {
+            names.push(arg);
+            continue;
         }
         op.print_descs = arg.contains('d');
         op.print_versions = arg.contains('v');
@@ -191,149 +364,469 @@ fn parse_args<T: ToString>(args: &[T]) -> CliOptions {
     op
 }
 
+//Wall-clock time spent in each phase, printed with --timing.
+#[derive(Default)]
+struct Timings {
+    phases: Vec<(&'static str, Duration)>,
+}
+
+impl Timings {
+    //Run f and add the time it took to the phase. Phases run once per install root are summed up.
+    fn time<T>(&mut self, phase: &'static str, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let out = f();
+        let elapsed = start.elapsed();
+        match self.phases.iter_mut().find(|(name, _)| *name == phase) {
+            Some((_, total)) => *total += elapsed,
+            None => self.phases.push((phase, elapsed)),
+        }
+        out
+    }
+
+    //Print the report to stderr, so it doesn't mix with the listing.
+    fn report(&self) {
+        let total: Duration = self.phases.iter().map(|(_, took)| *took).sum();
+        for (phase, took) in &self.phases {
+            eprintln!("{:>16}: {took:.2?}", phase.cyan());
+        }
+        eprintln!("{:>16}: {total:.2?}", "total".cyan().bold());
+    }
+}
+
+//The version, with the commit, build date and features build.rs recorded.
+fn version_text(json: bool) -> String {
+    let version = env!("CARGO_PKG_VERSION");
+    let commit = Some(env!("BUILD_COMMIT")).filter(|x| !x.is_empty());
+    let built = env!("BUILD_TIME")
+        .parse()
+        .map(|x| dates::ymd(UNIX_EPOCH + Duration::from_secs(x)))
+        .unwrap_or_default();
+    let features: Vec<&str> = env!("BUILD_FEATURES")
+        .split(',')
+        .filter(|x| !x.is_empty())
+        .collect();
+    if json {
+        let object = json::object(vec![
+            ("name", json::string(env!("CARGO_PKG_NAME"))),
+            ("version", json::string(version)),
+            (
+                "commit",
+                commit.map_or_else(|| String::from("null"), json::string),
+            ),
+            ("built", json::string(&built)),
+            ("features", json::strings(&features)),
+        ]);
+        return format!("{object}\n");
+    }
+    let features = if features.is_empty() {
+        String::from("none")
+    } else {
+        features.join(", ")
+    };
+    format!(
+        "{} {version}\ncommit: {}\nbuilt: {built}\nfeatures: {features}\n",
+        env!("CARGO_PKG_NAME"),
+        commit.unwrap_or("unknown")
+    )
+}
+
 fn main() {
     let args: Vec<String> = env::args().collect();
     //Parse command line arguments
     let options = parse_args(&args);
-    let (print_versions, print_descs, print_paths) = options.unpack();
+    let lang = options.lang.unwrap_or_else(Lang::detect);
+    let style = if options.iso {
+        Style::Iso
+    } else {
+        Style::detect()
+    };
+    crash::install(args.iter().skip(1).cloned().collect(), lang);
+    if options.plain {
+        colored::control::set_override(false);
+    } else if let Some(color) = term::color_override() {
+        colored::control::set_override(color);
+    }
+    if options.help {
+        print_help(lang);
+    }
+    if options.version {
+        print!("{}", version_text(options.json));
+        exit(0);
+    }
+    //Doctor explains why scanning finds what it does, so it runs even when it would fail.
+    if options.command == Command::Doctor {
+        exit(commands::doctor::run());
+    }
+    let (_, _, print_paths) = options.unpack();
+    let mut timings = Timings::default();
+
+    //Unreadable paths are reported once scanning is done, or right away if it can't start.
+    let mut warnings = Warnings::default();
 
     //Locate packages
-    let install_dirs = determine_pkgs_install_dir();
+    let install_dirs = timings.time("root discovery", || {
+        determine_pkgs_install_dir(&mut warnings)
+    });
     if install_dirs.is_empty() {
-        panic!("Failed to locate cargo root.");
+        warnings.print(options.verbose, lang, style);
+        eprintln!("{}", tr(lang, "err-no-root"));
+        exit(EXIT_NO_ROOT);
     };
     if print_paths {
         for dir in install_dirs {
-            println!("{dir}");
+            println!("{}", dir.display());
+        }
+        if options.timing {
+            timings.report();
         }
         exit(0);
     }
 
     let mut pkgs: Vec<String> = Vec::new();
-    let mut map: HashMap<String, (String, String)> = HashMap::new();
-    for dir in install_dirs {
+    let mut map: HashMap<String, PkgInfo> = HashMap::new();
+    //Plain mode is meant for screen readers, which would read every redraw.
+    let mut progress = Progress::new("manifests", !(options.no_progress || options.plain));
+    for dir in &install_dirs {
         //Get the list of installed packages
-        if let Some(mut pkgs_) = list_pkgs(&dir) {
+        if let Some(mut pkgs_) = timings.time("bin listing", || {
+            list_pkgs(dir, options.all_files, &mut warnings)
+        }) {
             pkgs.append(&mut pkgs_);
         };
         //Get packages' descriptions and versions
-        if let Some(map_) = get_pkgs_info(&dir) {
+        if let Some(map_) = timings.time("manifest parsing", || {
+            get_pkgs_info(dir, &mut progress, &mut warnings)
+        }) {
             map.extend(map_);
         };
     }
+    //JSON has them in the document, so automation gets them with the packages.
+    if !options.json {
+        warnings.print(options.verbose, lang, style);
+    }
+
+    //Keep the listing in the same order on every system, read_dir doesn't guarantee any.
+    pkgs.sort();
 
     if pkgs.is_empty() {
-        panic!("Failed to list packages.");
+        eprintln!("{}", tr(lang, "err-no-pkgs"));
+        exit(EXIT_NOTHING_INSTALLED);
     }
     if map.is_empty() {
-        panic!("Failed to get info.");
+        eprintln!("{}", tr(lang, "err-no-info"));
+        exit(EXIT_ERROR);
     }
 
-    //Print info out
-    for mut pkg in pkgs {
-        //Get package description
-        let (mut ver, mut desc) = map
-            .get(&pkg)
-            .cloned()
-            .map_or_else(|| (String::from("n/a"), String::from("n/a")), |o| o);
-        if let Some(i) = pkg.find(".exe") {
-            pkg = pkg[..i].to_owned()
-        }
-
-        //If user passed -v print version info, additionally if -d is passed print package
-        //descriptions.
-        if print_versions {
-            ver = format!(" {}", ver.yellow());
-        } else {
-            ver = String::new();
-        }
-        if print_descs {
-            desc = format!(" {}", desc.blue());
-        } else {
-            desc = String::new();
-        }
-        print!("{}{}{} ", pkg.green().bold(), ver, desc);
-        if print_descs || print_versions {
-            println!();
-        }
+    let stars = stars::load();
+    //Starring works on every binary, not only the starred ones.
+    let starring = matches!(options.command, Command::Star(_) | Command::Unstar(_));
+    if options.starred && !starring {
+        pkgs.retain(|x| stars.iter().any(|star| star == paths::bin_name(x)));
     }
 
-    if !(print_descs || print_versions) {
-        println!();
-    }
+    let mut cx = Context {
+        options,
+        lang,
+        style,
+        install_dirs,
+        pkgs,
+        map,
+        stars,
+        timings,
+        warnings,
+    };
+    exit(commands::run(&mut cx));
 }
 
 mod test {
-    #[test]
-    fn determine_pkgs_install_dir() {
-        std::env::set_var("CARGO_INSTALL_ROOT", "tmp/CIR_PATH");
-        std::env::set_var("CARGO_HOME", "tmp/CH_PATH");
-        std::env::set_var("HOME", "tmp/H_PATH");
-        std::fs::create_dir("tmp/").unwrap();
-        std::fs::create_dir("tmp/CIR_PATH").unwrap();
-        std::fs::create_dir("tmp/CH_PATH").unwrap();
-        std::fs::create_dir_all("tmp/H_PATH/.cargo").unwrap();
-        let out = crate::determine_pkgs_install_dir();
-        assert_eq!(
-            out,
-            vec![
-                String::from("tmp/CIR_PATH"),
-                String::from("tmp/CH_PATH"),
-                String::from("tmp/H_PATH/.cargo")
-            ]
-        );
-        std::fs::remove_dir_all("tmp/").unwrap();
-    }
-
     #[test]
     fn parse_args() {
+        //The first argument is the program name and gets skipped.
         assert_eq!(
-            crate::parse_args(&["vdp"]),
+            crate::parse_args(&["ls-crates", "vdp"]),
             crate::CliOptions {
                 print_versions: true,
                 print_descs: true,
-                print_paths: true
+                print_paths: true,
+                ..Default::default()
             }
         );
         assert_eq!(
-            crate::parse_args(&["vd"]),
+            crate::parse_args(&["ls-crates", "vd"]),
             crate::CliOptions {
                 print_versions: true,
                 print_descs: true,
-                print_paths: false
+                print_paths: false,
+                ..Default::default()
             }
         );
         assert_eq!(
-            crate::parse_args(&["dp"]),
+            crate::parse_args(&["ls-crates", "dp"]),
             crate::CliOptions {
                 print_versions: false,
                 print_descs: true,
-                print_paths: true
+                print_paths: true,
+                ..Default::default()
             }
         );
         assert_eq!(
-            crate::parse_args(&["vp"]),
+            crate::parse_args(&["ls-crates", "vp"]),
             crate::CliOptions {
                 print_versions: true,
                 print_descs: false,
-                print_paths: true
+                print_paths: true,
+                ..Default::default()
             }
         );
         assert_eq!(
-            crate::parse_args(&[""]),
+            crate::parse_args(&["ls-crates", ""]),
             crate::CliOptions {
                 print_versions: false,
                 print_descs: false,
-                print_paths: false
+                print_paths: false,
+                ..Default::default()
             }
         );
         assert_eq!(
-            crate::parse_args(&["asfv"]),
+            crate::parse_args(&["ls-crates", "asfv"]),
             crate::CliOptions {
                 print_versions: true,
                 print_descs: false,
-                print_paths: false
+                print_paths: false,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            crate::parse_args(&["cargo-ls-crates", "ls-crates", "debug-info"]),
+            crate::CliOptions {
+                command: crate::Command::Doctor,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            crate::parse_args(&["ls-crates", "--lang", "pl", "-h"]),
+            crate::CliOptions {
+                help: true,
+                lang: Some(crate::Lang::Pl),
+                ..Default::default()
             }
         );
+        assert_eq!(
+            crate::parse_args(&["ls-crates", "--lang=en_US.UTF-8"]),
+            crate::CliOptions {
+                lang: Some(crate::Lang::En),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            crate::parse_args(&["ls-crates", "check", "--against", "team.toml"]),
+            crate::CliOptions {
+                command: crate::Command::Check,
+                against: Some(String::from("team.toml")),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            crate::parse_args(&[
+                "ls-crates",
+                "check",
+                "--against",
+                "https://example.com/team.toml",
+                "--sha256",
+                "67d92db0"
+            ]),
+            crate::CliOptions {
+                command: crate::Command::Check,
+                against: Some(String::from("https://example.com/team.toml")),
+                sha256: Some(String::from("67d92db0")),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            crate::parse_args(&[
+                "ls-crates",
+                "update",
+                "--target",
+                "x86_64-unknown-linux-musl",
+                "rg"
+            ]),
+            crate::CliOptions {
+                command: crate::Command::Update(vec![String::from("rg")]),
+                target: Some(String::from("x86_64-unknown-linux-musl")),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            crate::parse_args(&["ls-crates", "deps", "rg"]),
+            crate::CliOptions {
+                command: crate::Command::Deps(String::from("rg")),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            crate::parse_args(&["ls-crates", "deps", "--diff", "rg", "fd"]),
+            crate::CliOptions {
+                command: crate::Command::DepsDiff(String::from("rg"), String::from("fd")),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            crate::parse_args(&["ls-crates", "export", "--devcontainer"]),
+            crate::CliOptions {
+                command: crate::Command::Export,
+                devcontainer: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            crate::parse_args(&["ls-crates", "groups"]),
+            crate::CliOptions {
+                command: crate::Command::Groups,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            crate::parse_args(&["ls-crates", "star", "rg", "--starred"]),
+            crate::CliOptions {
+                command: crate::Command::Star(String::from("rg")),
+                starred: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            crate::parse_args(&["ls-crates", "man", "dust", "--docs"]),
+            crate::CliOptions {
+                command: crate::Command::Man(String::from("dust")),
+                docs: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            crate::parse_args(&[
+                "ls-crates",
+                "cheatsheet",
+                "--html",
+                "--by-category",
+                "--all-source-versions",
+                "--crate-names"
+            ]),
+            crate::CliOptions {
+                command: crate::Command::Cheatsheet,
+                html: true,
+                by_category: true,
+                all_source_versions: true,
+                crate_names: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            crate::parse_args(&["ls-crates", "-d", "--width", "60"]).width,
+            Some(60)
+        );
+        assert_eq!(
+            crate::parse_args(&["ls-crates", "--width=80"]).width,
+            Some(80)
+        );
+        assert_eq!(
+            crate::parse_args(&["ls-crates", "--width=wide"]).width,
+            None
+        );
+        assert!(crate::parse_args(&["ls-crates", "--iso"]).iso);
+        assert!(crate::parse_args(&["ls-crates", "--absolute-time"]).absolute_time);
+        assert_eq!(
+            crate::parse_args(&["ls-crates", "--version", "--output", "json"]),
+            crate::CliOptions {
+                version: true,
+                json: true,
+                ..Default::default()
+            }
+        );
+        assert!(!crate::parse_args(&["ls-crates", "--version", "--output=toml"]).json);
+        assert!(crate::parse_args(&["ls-crates", "--starred", "--json"]).json);
+        assert_eq!(
+            crate::parse_args(&["ls-crates", "update", "rg", "just", "--plain"]),
+            crate::CliOptions {
+                command: crate::Command::Update(vec![String::from("rg"), String::from("just")]),
+                plain: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            crate::parse_args(&["ls-crates", "update", "--no-locked", "rg"]),
+            crate::CliOptions {
+                command: crate::Command::Update(vec![String::from("rg")]),
+                locked: Some(false),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            crate::parse_args(&["ls-crates", "update", "--locked"]).locked,
+            Some(true)
+        );
+        assert_eq!(
+            crate::parse_args(&["ls-crates", "uninstall", "--dry-run", "rg", "--yes"]),
+            crate::CliOptions {
+                command: crate::Command::Uninstall(vec![String::from("rg")]),
+                dry_run: true,
+                yes: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            crate::parse_args(&["ls-crates", "list", "-v"]),
+            crate::CliOptions {
+                print_versions: true,
+                ..Default::default()
+            }
+        );
+        for args in [["ls-crates", "outdated"], ["ls-crates", "--outdated"]] {
+            assert_eq!(crate::parse_args(&args).command, crate::Command::Outdated);
+        }
+        for args in [["ls-crates", "doctor"], ["ls-crates", "debug-info"]] {
+            assert_eq!(crate::parse_args(&args).command, crate::Command::Doctor);
+        }
+        //A command missing its argument is ignored like other invalid arguments.
+        assert_eq!(
+            crate::parse_args(&["ls-crates", "deps"]).command,
+            crate::Command::List
+        );
+        //Long options don't toggle the short flags.
+        assert_eq!(
+            crate::parse_args(&[
+                "ls-crates",
+                "-v",
+                "--timing",
+                "--plain",
+                "--all-files",
+                "--build-info",
+                "--no-progress",
+                "--verbose",
+                "--compare-system"
+            ]),
+            crate::CliOptions {
+                print_versions: true,
+                build_info: true,
+                no_progress: true,
+                verbose: true,
+                compare_system: true,
+                timing: true,
+                plain: true,
+                all_files: true,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn version_text() {
+        let text = crate::version_text(false);
+        assert!(text.starts_with(&format!(
+            "cargo-ls-crates {}\ncommit: ",
+            env!("CARGO_PKG_VERSION")
+        )));
+        assert!(text.ends_with("\nfeatures: none\n"));
+        let json = crate::version_text(true);
+        assert!(json.starts_with("{\"built\": "));
+        assert!(json.contains(", \"features\": [], \"name\": \"cargo-ls-crates\", \"version\": "));
     }
 }
@@ -0,0 +1,109 @@
+//Show the manual of an installed tool: its man page when there's one installed or shipped with the
+//crate's sources, its --help output otherwise.
+
+use crate::PkgInfo;
+use std::{
+    env, fs,
+    io::{self, IsTerminal, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+//Look for `<bin>.1` or `<crate>.1` in the crate's sources, a few levels deep, since crates keep
+//their pages in directories like doc/ or man/.
+pub fn find_page(bin: &str, info: &PkgInfo) -> Option<PathBuf> {
+    let names = [format!("{bin}.1"), format!("{}.1", info.name)];
+    let mut dirs = vec![(info.path.clone(), 0)];
+    while let Some((dir, depth)) = dirs.pop() {
+        for entry in fs::read_dir(&dir).into_iter().flatten().flatten() {
+            let path = entry.path();
+            if path.is_dir() && depth < 3 {
+                dirs.push((path, depth + 1));
+            } else if names.iter().any(|x| entry.file_name() == x.as_str()) {
+                return Some(path);
+            }
+        }
+    }
+    None
+}
+
+//Run `man` for an installed page, then for one from the sources. Returns false if neither worked.
+fn run_man(bin: &str, page: Option<&Path>) -> bool {
+    let installed = Command::new("man")
+        .args(["-w", bin])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|x| x.success());
+    let status = if installed {
+        Command::new("man").arg(bin).status()
+    } else if let Some(page) = page {
+        //man reads a file instead of looking up a page when the argument contains a slash.
+        Command::new("man").arg(page).status()
+    } else {
+        return false;
+    };
+    status.is_ok_and(|x| x.success())
+}
+
+//Write text through $PAGER (less by default) when printing to a terminal, and directly otherwise
+//or when the pager can't be started.
+fn page(text: &[u8]) -> io::Result<()> {
+    if io::stdout().is_terminal() {
+        let pager = env::var("PAGER").unwrap_or_else(|_| String::from("less"));
+        let mut args = pager.split_whitespace();
+        if let Some(program) = args.next() {
+            if let Ok(mut child) = Command::new(program)
+                .args(args)
+                .stdin(Stdio::piped())
+                .spawn()
+            {
+                if let Some(mut stdin) = child.stdin.take() {
+                    //The user can quit the pager before reading everything.
+                    let _ = stdin.write_all(text);
+                }
+                child.wait()?;
+                return Ok(());
+            }
+        }
+    }
+    io::stdout().write_all(text)
+}
+
+pub fn show(bin: &str, bin_path: &Path, info: Option<&PkgInfo>) -> io::Result<()> {
+    let page_in_sources = info.and_then(|x| find_page(bin, x));
+    if run_man(bin, page_in_sources.as_deref()) {
+        return Ok(());
+    }
+    let output = Command::new(bin_path).arg("--help").output()?;
+    //Some tools print their help to stderr.
+    if output.stdout.is_empty() {
+        page(&output.stderr)
+    } else {
+        page(&output.stdout)
+    }
+}
+
+mod test {
+    #[test]
+    fn find_page() {
+        //Unique, so runs at the same time don't share it.
+        let root =
+            std::env::temp_dir().join(format!("cargo-ls-crates-find-page-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("doc/man")).unwrap();
+        std::fs::write(root.join("doc/man/rg.1"), "").unwrap();
+        std::fs::write(root.join("README.md"), "").unwrap();
+        let info = crate::PkgInfo {
+            name: String::from("ripgrep"),
+            path: root.clone(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            super::find_page("rg", &info),
+            Some(root.join("doc/man/rg.1"))
+        );
+        assert_eq!(super::find_page("rga", &info), None);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
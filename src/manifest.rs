@@ -0,0 +1,378 @@
+//Reading fields out of Cargo.toml files. Only the handful of fields the listing needs are supported,
+//so there's no need for a full TOML parser.
+
+use std::{fs, path::Path};
+
+//Get the text following `key =` on the line where the key is defined, up to the end of the manifest.
+fn value<'a>(cargo_toml_content: &'a str, key: &str) -> Option<&'a str> {
+    let mut offset = 0;
+    for line in cargo_toml_content.split_inclusive('\n') {
+        let rest = line
+            .trim_start()
+            .strip_prefix(key)
+            .and_then(|x| x.trim_start().strip_prefix('='));
+        if let Some(rest) = rest {
+            let start = offset + line.len() - rest.len();
+            return Some(cargo_toml_content[start..].trim_start_matches([' ', '\t']));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+//Get a string field like `description = "..."`. All four kinds of TOML strings are supported.
+pub fn string(cargo_toml_content: &str, key: &str) -> Option<String> {
+    let value = value(cargo_toml_content, key)?;
+    if let Some(rest) = value.strip_prefix("\"\"\"") {
+        unescape(skip_newline(rest), "\"\"\"")
+    } else if let Some(rest) = value.strip_prefix('"') {
+        unescape(rest, "\"")
+    } else if let Some(rest) = value.strip_prefix("'''") {
+        let rest = skip_newline(rest);
+        Some(rest[..rest.find("'''")?].to_string())
+    } else if let Some(rest) = value.strip_prefix('\'') {
+        let end = rest.find(['\'', '\n'])?;
+        rest[end..]
+            .starts_with('\'')
+            .then(|| rest[..end].to_string())
+    } else {
+        None
+    }
+}
+
+//A newline right after the opening delimiter of a multi-line string isn't part of it.
+fn skip_newline(s: &str) -> &str {
+    s.strip_prefix("\r\n")
+        .or_else(|| s.strip_prefix('\n'))
+        .unwrap_or(s)
+}
+
+//Read a basic string up to the closing delimiter, resolving escape sequences.
+fn unescape(s: &str, delimiter: &str) -> Option<String> {
+    let multiline = delimiter.len() > 1;
+    let mut out = String::new();
+    let mut chars = s.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if s[i..].starts_with(delimiter) {
+            return Some(out);
+        }
+        if c == '\n' && !multiline {
+            return None;
+        }
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        let (_, escaped) = chars.next()?;
+        match escaped {
+            'n' => out.push('\n'),
+            't' => out.push('\t'),
+            'r' => out.push('\r'),
+            'b' => out.push('\u{8}'),
+            'f' => out.push('\u{c}'),
+            'e' => out.push('\u{1b}'),
+            '"' => out.push('"'),
+            '\\' => out.push('\\'),
+            'u' | 'U' => {
+                let len = if escaped == 'u' { 4 } else { 8 };
+                let hex: String = (0..len).filter_map(|_| chars.next()).map(|x| x.1).collect();
+                out.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+            }
+            //A backslash at the end of a line in a multi-line string trims the line break and the
+            //whitespace after it.
+            c if multiline && c.is_whitespace() => {
+                while chars.next_if(|x| x.1.is_whitespace()).is_some() {}
+            }
+            _ => return None,
+        }
+    }
+    None
+}
+
+//Get a string array field like `categories = ["a", "b"]`.
+pub fn array(cargo_toml_content: &str, key: &str) -> Vec<String> {
+    let Some(array) = value(cargo_toml_content, key) else {
+        return Vec::new();
+    };
+    let (Some(start), Some(end)) = (array.strip_prefix('['), array.find(']')) else {
+        return Vec::new();
+    };
+    start[..end - 1]
+        .split(',')
+        .map(|x| x.trim().trim_matches(['"', '\'']))
+        .filter(|x| !x.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+//Collapse line breaks and runs of whitespace, so a value fits on one line.
+pub fn normalize_whitespace(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+//Check for `key.workspace = true` or `key = { workspace = true }`.
+pub fn inherits_from_workspace(cargo_toml_content: &str, key: &str) -> bool {
+    cargo_toml_content.lines().any(|line| {
+        let line: String = line.split_whitespace().collect();
+        line == format!("{key}.workspace=true") || line == format!("{key}={{workspace=true}}")
+    })
+}
+
+//Get a table like `[package]` with its header, up to where the next one starts.
+pub fn table<'a>(cargo_toml_content: &'a str, header: &str) -> Option<&'a str> {
+    let start = cargo_toml_content
+        .match_indices(header)
+        .map(|(i, _)| i)
+        .find(|&i| i == 0 || cargo_toml_content[..i].ends_with('\n'))?;
+    let table = &cargo_toml_content[start..];
+    let end = table[1..].find("\n[").map_or(table.len(), |x| x + 1);
+    Some(&table[..end])
+}
+
+//Find the workspace root of a package and get its [workspace.package] table, which holds the
+//fields members can inherit. The package can be the root itself. The search stops at `root`, the
+//top of the checkout, so it never reaches an unrelated project the checkout happens to be in.
+pub fn workspace_package_table(pkg_dir: &Path, root: &Path) -> Option<String> {
+    for dir in pkg_dir.ancestors().take_while(|x| x.starts_with(root)) {
+        let Ok(cargo_toml_content) = fs::read_to_string(dir.join("Cargo.toml")) else {
+            continue;
+        };
+        if let Some(table) = table(&cargo_toml_content, "[workspace.package]") {
+            return Some(table.to_string());
+        }
+    }
+    None
+}
+
+mod test {
+    #[test]
+    fn string() {
+        use super::string;
+        let toml = r#"[package]
+name = "basic"
+descriptions = "not this one"
+description.workspace = false
+description = "Quotes \"and\" tabs\tand \u00e9 # not a comment" # a comment
+homepage = 'C:\literal'
+readme = """
+Line one
+Line two \
+    joined"""
+license = '''
+raw \n'''
+broken = "unterminated
+"#;
+        assert_eq!(
+            string(toml, "description").as_deref(),
+            Some("Quotes \"and\" tabs\tand é # not a comment")
+        );
+        assert_eq!(string(toml, "name").as_deref(), Some("basic"));
+        assert_eq!(string(toml, "homepage").as_deref(), Some("C:\\literal"));
+        assert_eq!(
+            string(toml, "readme").as_deref(),
+            Some("Line one\nLine two joined")
+        );
+        assert_eq!(string(toml, "license").as_deref(), Some("raw \\n"));
+        assert_eq!(string(toml, "broken"), None);
+        assert_eq!(string(toml, "missing"), None);
+    }
+
+    #[test]
+    fn array() {
+        let toml = "keywords = [\"cli\", 'json']\ncategories = [\n    \"command-line-utilities\",\n    \"text-processing\",\n]\n";
+        assert_eq!(super::array(toml, "keywords"), vec!["cli", "json"]);
+        assert_eq!(
+            super::array(toml, "categories"),
+            vec!["command-line-utilities", "text-processing"]
+        );
+        assert!(super::array(toml, "authors").is_empty());
+    }
+
+    #[test]
+    fn normalize_whitespace() {
+        assert_eq!(
+            super::normalize_whitespace("  A tool\n  spanning\tlines  "),
+            "A tool spanning lines"
+        );
+    }
+
+    #[test]
+    fn workspace_package_table() {
+        //Unique, so runs at the same time don't share it.
+        let root = std::env::temp_dir().join(format!(
+            "cargo-ls-crates-workspace-package-table-{}",
+            std::process::id()
+        ));
+        //Laid out like a crate installed with `cargo install --git`, in a home directory that's a
+        //project of its own.
+        let checkout = root.join("git/checkouts/tools-1a2b3c4d5e6f7a8b/0f1e2d3");
+        let member = checkout.join("crates").join("member");
+        std::fs::create_dir_all(&member).unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[workspace.package]\ndescription = \"Unrelated\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            checkout.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.package]\nversion = \"1.2.3\"\ndescription = \"Shared description\"\nkeywords = [\"cli\"]\n\n[workspace.dependencies]\nregex = \"1\"\n",
+        )
+        .unwrap();
+        let member_toml = "[package]\nname = \"member\"\nversion.workspace = true\ndescription = { workspace = true }\n";
+        std::fs::write(member.join("Cargo.toml"), member_toml).unwrap();
+
+        assert!(super::inherits_from_workspace(member_toml, "description"));
+        assert!(super::inherits_from_workspace(member_toml, "version"));
+        assert!(!super::inherits_from_workspace(member_toml, "name"));
+        let table = super::workspace_package_table(&member, &checkout).unwrap();
+        assert_eq!(
+            super::string(&table, "description"),
+            Some(String::from("Shared description"))
+        );
+        assert_eq!(super::array(&table, "keywords"), vec!["cli"]);
+        //Only the [workspace.package] table is returned.
+        assert_eq!(super::string(&table, "regex"), None);
+        //The root of the workspace can be a package too.
+        assert_eq!(
+            super::workspace_package_table(&checkout, &checkout),
+            Some(table)
+        );
+        //Nothing above the checkout is read.
+        assert_eq!(super::workspace_package_table(&member, &member), None);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn table() {
+        let toml = "[workspace]\nmembers = [\"a\"]\n\n[package]\nname = \"a\"\n[dependencies]\nb = \"1\"\n";
+        assert_eq!(
+            super::table(toml, "[package]"),
+            Some("[package]\nname = \"a\"")
+        );
+        assert_eq!(
+            super::table(toml, "[workspace]"),
+            Some("[workspace]\nmembers = [\"a\"]\n")
+        );
+        assert_eq!(super::table(toml, "[workspace.package]"), None);
+    }
+
+    //A xorshift generator, so a failure can be reproduced from its seed.
+    #[cfg(test)]
+    struct Rng(u64);
+
+    #[cfg(test)]
+    impl Rng {
+        fn below(&mut self, n: usize) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 % n as u64) as usize
+        }
+
+        fn pick<'a>(&mut self, x: &[&'a str]) -> &'a str {
+            x[self.below(x.len())]
+        }
+    }
+
+    //Malformed manifests must never panic, whatever is cut out of or spliced into them.
+    #[test]
+    fn malformed_manifests() {
+        let valid = r#"[package]
+name = "tool"
+version.workspace = true
+description = """
+  A \"tool\" é \
+  more"""
+homepage = '''
+raw'''
+categories = ["a", 'b',
+]
+keywords = []
+"#;
+        let pieces = [
+            "\"",
+            "'",
+            "\"\"\"",
+            "'''",
+            "\\",
+            "\\u",
+            "\\U0001F600",
+            "\\x",
+            "=",
+            "[",
+            "]",
+            ",",
+            "\n",
+            "\r\n",
+            " ",
+            "\t",
+            "#",
+            "é",
+            "description",
+            "categories",
+            "{ workspace = true }",
+        ];
+        let keys = [
+            "name",
+            "description",
+            "homepage",
+            "categories",
+            "keywords",
+            "version",
+        ];
+        for seed in 1..2000 {
+            let mut rng = Rng(seed);
+            let mut content = valid.to_string();
+            for _ in 0..rng.below(8) {
+                let mut at = rng.below(content.len() + 1);
+                while !content.is_char_boundary(at) {
+                    at -= 1;
+                }
+                match rng.below(3) {
+                    0 => content.truncate(at),
+                    1 => content.insert_str(at, rng.pick(&pieces)),
+                    _ => {
+                        let end = (at + rng.below(10)).min(content.len());
+                        if content.is_char_boundary(end) {
+                            content.replace_range(at..end, "");
+                        }
+                    }
+                }
+            }
+            for key in keys {
+                let _ = super::string(&content, key).map(|x| super::normalize_whitespace(&x));
+                let _ = super::array(&content, key);
+                let _ = super::inherits_from_workspace(&content, key);
+            }
+        }
+    }
+
+    //Any string escaped the way cargo writes it reads back unchanged.
+    #[test]
+    fn escaped_strings_round_trip() {
+        let chars = [
+            "a", " ", "\"", "\\", "'", "\n", "\t", "é", "😀", "#", "=", "\u{7f}",
+        ];
+        for seed in 1..2000 {
+            let mut rng = Rng(seed);
+            let original: String = (0..rng.below(20)).map(|_| rng.pick(&chars)).collect();
+            let escaped: String = original
+                .chars()
+                .map(|x| match x {
+                    '"' => String::from("\\\""),
+                    '\\' => String::from("\\\\"),
+                    '\n' => String::from("\\n"),
+                    '\t' => String::from("\\t"),
+                    x if x.is_control() => format!("\\u{:04x}", x as u32),
+                    x => x.to_string(),
+                })
+                .collect();
+            let toml = format!("description = \"{escaped}\"\n");
+            assert_eq!(
+                super::string(&toml, "description"),
+                Some(original),
+                "seed {seed}"
+            );
+        }
+    }
+}
@@ -0,0 +1,204 @@
+//Fetching over HTTPS, mainly to find crates with newer versions on crates.io. There's no HTTP
+//client among the dependencies, so curl does it, which is there on nearly every system cargo is.
+
+use crate::{
+    i18n::{tr, Lang},
+    json,
+    progress::Progress,
+    version,
+};
+use colored::Colorize;
+use std::{
+    cmp::Ordering,
+    io,
+    process::{Command, Stdio},
+};
+
+const INDEX: &str = "https://index.crates.io";
+
+//How many requests run at once.
+const PARALLEL: usize = 8;
+
+//Whether a crate recorded with this source was installed from crates.io. Crates without a
+//recorded source came from registry/src, which is almost always crates.io.
+pub fn is_crates_io(source: &str) -> bool {
+    source.is_empty()
+        || source == "registry+https://github.com/rust-lang/crates.io-index"
+        || source == "sparse+https://index.crates.io/"
+}
+
+//Where the index file of a crate is, following the layout of cargo's registry index.
+//source: https://doc.rust-lang.org/cargo/reference/registry-index.html#index-files
+fn index_url(name: &str) -> String {
+    let name = name.to_lowercase();
+    let path = match name.len() {
+        1 => format!("1/{name}"),
+        2 => format!("2/{name}"),
+        3 => format!("3/{}/{name}", &name[..1]),
+        _ => format!("{}/{}/{name}", &name[..2], &name[2..4]),
+    };
+    format!("{INDEX}/{path}")
+}
+
+//The newest version that isn't yanked, from an index file with a JSON object per line.
+//Pre-releases only count for crates that have a pre-release installed.
+fn latest(index: &str, installed: &str) -> Option<String> {
+    let pre = installed.contains('-');
+    index
+        .lines()
+        .filter_map(json::parse)
+        .filter(|x| x.get("yanked") != Some(&json::Value::Bool(true)))
+        .filter_map(|x| x.get("vers")?.as_str().map(String::from))
+        .filter(|x| pre || !x.split('+').next().unwrap_or_default().contains('-'))
+        .max_by(|a, b| version::compare(a, b))
+}
+
+//curl printing only the body, or an error and failing on HTTP errors. Redirects are followed, but
+//never off HTTPS.
+fn curl(url: &str) -> Command {
+    let mut command = Command::new("curl");
+    command
+        .args(["--silent", "--show-error", "--fail", "--location"])
+        .args(["--proto", "=https", "--max-time", "30", "--user-agent"])
+        .arg(concat!(
+            env!("CARGO_PKG_NAME"),
+            "/",
+            env!("CARGO_PKG_VERSION")
+        ))
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    command
+}
+
+//The body of a single URL, or curl's error.
+pub fn get(url: &str) -> Result<Vec<u8>, String> {
+    let output = curl(url).output().map_err(|e| format!("curl: {e}"))?;
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+//Fetch the index files, a few at a time. Failing to start curl at all is an error, a failed
+//request only for that crate.
+fn fetch(names: &[&str], progress: &mut Progress) -> io::Result<Vec<Result<String, String>>> {
+    let mut results = Vec::new();
+    progress.start(names.len());
+    for chunk in names.chunks(PARALLEL) {
+        let children = chunk
+            .iter()
+            .map(|name| curl(&index_url(name)).spawn())
+            .collect::<io::Result<Vec<_>>>()?;
+        for child in children {
+            let output = child.wait_with_output()?;
+            progress.tick();
+            results.push(if output.status.success() {
+                Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+            } else {
+                Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+            });
+        }
+    }
+    progress.finish();
+    Ok(results)
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Outdated {
+    pub name: String,
+    pub installed: String,
+    pub latest: String,
+}
+
+//Crate name and why its latest version couldn't be looked up.
+pub type Failed = (String, String);
+
+//Crates with a newer version on crates.io, and the ones that couldn't be looked up.
+pub fn outdated(
+    crates: &[(&str, &str)],
+    progress: &mut Progress,
+) -> io::Result<(Vec<Outdated>, Vec<Failed>)> {
+    let names: Vec<&str> = crates.iter().map(|x| x.0).collect();
+    let mut outdated = Vec::new();
+    let mut failed = Vec::new();
+    for ((name, installed), index) in crates.iter().zip(fetch(&names, progress)?) {
+        let latest = match index {
+            Ok(x) => latest(&x, installed),
+            Err(e) => {
+                failed.push((name.to_string(), e));
+                continue;
+            }
+        };
+        let Some(latest) = latest else { continue };
+        if version::compare(&latest, installed) == Ordering::Greater {
+            outdated.push(Outdated {
+                name: name.to_string(),
+                installed: installed.to_string(),
+                latest,
+            });
+        }
+    }
+    Ok((outdated, failed))
+}
+
+//A warning for each crate that couldn't be looked up.
+pub fn warn(failed: &[Failed], lang: Lang) {
+    for (name, e) in failed {
+        eprintln!(
+            "{}: {} {name}: {e}",
+            tr(lang, "warn-label").yellow(),
+            tr(lang, "warn-no-latest")
+        );
+    }
+}
+
+pub fn print(outdated: &[Outdated], failed: &[Failed], lang: Lang) {
+    warn(failed, lang);
+    if outdated.is_empty() && failed.is_empty() {
+        eprintln!("{}", tr(lang, "msg-up-to-date"));
+    }
+    for x in outdated {
+        println!(
+            "{} {} → {}",
+            x.name.bold(),
+            x.installed.yellow(),
+            x.latest.green()
+        );
+    }
+}
+
+mod test {
+    #[test]
+    fn index_url() {
+        assert_eq!(super::index_url("a"), "https://index.crates.io/1/a");
+        assert_eq!(super::index_url("fd"), "https://index.crates.io/2/fd");
+        assert_eq!(super::index_url("bat"), "https://index.crates.io/3/b/bat");
+        assert_eq!(
+            super::index_url("ripgrep"),
+            "https://index.crates.io/ri/pg/ripgrep"
+        );
+        assert_eq!(
+            super::index_url("Inflector"),
+            "https://index.crates.io/in/fl/inflector"
+        );
+    }
+
+    #[test]
+    fn latest() {
+        let index = concat!(
+            "{\"name\":\"ripgrep\",\"vers\":\"13.0.0\",\"yanked\":false}\n",
+            "{\"name\":\"ripgrep\",\"vers\":\"14.1.0\",\"yanked\":false}\n",
+            "{\"name\":\"ripgrep\",\"vers\":\"14.2.0\",\"yanked\":true}\n",
+            "{\"name\":\"ripgrep\",\"vers\":\"15.0.0-beta.1\",\"yanked\":false}\n",
+        );
+        assert_eq!(super::latest(index, "13.0.0").as_deref(), Some("14.1.0"));
+        assert_eq!(
+            super::latest(index, "14.0.0-rc.1").as_deref(),
+            Some("15.0.0-beta.1")
+        );
+        assert_eq!(super::latest("", "1.0.0"), None);
+    }
+}
@@ -0,0 +1,120 @@
+//The listing as data, for --json. Written the same way on every run, with packages, binaries and
+//keys sorted, so it diffs cleanly when committed.
+
+use crate::{json, PkgInfo};
+use std::path::PathBuf;
+
+#[derive(Debug, PartialEq)]
+pub struct Package {
+    //Crate name, or the binary's own name when there's no metadata for it.
+    pub name: String,
+    pub version: Option<String>,
+    //None when there's no metadata or the sources aren't in registry/src.
+    pub description: Option<String>,
+    pub binaries: Vec<String>,
+    //Install root the binaries are in.
+    pub root: PathBuf,
+}
+
+impl Package {
+    pub fn to_json(&self) -> String {
+        let optional = |x: &Option<String>| {
+            x.as_deref()
+                .map_or_else(|| String::from("null"), json::string)
+        };
+        json::object(vec![
+            ("name", json::string(&self.name)),
+            ("version", optional(&self.version)),
+            ("description", optional(&self.description)),
+            ("binaries", json::strings(&self.binaries)),
+            ("root", json::string(&self.root.to_string_lossy())),
+        ])
+    }
+}
+
+//One package per crate and root, with every binary it installed there, sorted by name and root.
+pub fn packages<'a>(
+    installed: impl IntoIterator<Item = (&'a str, Option<&'a PkgInfo>, PathBuf)>,
+) -> Vec<Package> {
+    let mut packages: Vec<Package> = Vec::new();
+    for (bin, info, root) in installed {
+        let name = info.map_or(bin, |x| x.name.as_str());
+        match packages
+            .iter_mut()
+            .find(|x| x.name == name && x.root == root && info.is_some())
+        {
+            Some(package) => package.binaries.push(bin.to_string()),
+            None => packages.push(Package {
+                name: name.to_string(),
+                version: info.map(|x| x.version.clone()),
+                description: info
+                    .map(|x| x.description.clone())
+                    .filter(|x| !x.is_empty()),
+                binaries: vec![bin.to_string()],
+                root,
+            }),
+        }
+    }
+    for package in &mut packages {
+        package.binaries.sort();
+        package.binaries.dedup();
+    }
+    packages.sort_by(|a, b| (&a.name, &a.root).cmp(&(&b.name, &b.root)));
+    packages
+}
+
+//An array with an item on each line, so the document diffs cleanly.
+fn lines(items: &[String]) -> String {
+    if items.is_empty() {
+        return String::from("[]");
+    }
+    format!("[\n    {}\n  ]", items.join(",\n    "))
+}
+
+//The packages, and what couldn't be read while looking for them, so a partial listing can be told
+//apart from a complete one.
+pub fn to_json(packages: &[Package], errors: &[String]) -> String {
+    let packages: Vec<String> = packages.iter().map(Package::to_json).collect();
+    format!(
+        "{{\n  \"errors\": {},\n  \"packages\": {}\n}}\n",
+        lines(errors),
+        lines(&packages)
+    )
+}
+
+mod test {
+    #[test]
+    fn packages() {
+        use std::path::PathBuf;
+        let rg = crate::PkgInfo {
+            name: String::from("ripgrep"),
+            version: String::from("13.0.0"),
+            description: String::from("Search \"fast\""),
+            ..Default::default()
+        };
+        let root = PathBuf::from("/home/user/.cargo");
+        let packages = super::packages([
+            ("script", None, root.clone()),
+            ("rga", Some(&rg), root.clone()),
+            ("rg", Some(&rg), root.clone()),
+        ]);
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[0].binaries, ["rg", "rga"]);
+        assert_eq!(
+            super::to_json(&packages, &[]),
+            "{\n  \"errors\": [],\n  \"packages\": [\n    {\"binaries\": [\"rg\", \"rga\"], \"description\": \"Search \\\"fast\\\"\", \"name\": \"ripgrep\", \"root\": \"/home/user/.cargo\", \"version\": \"13.0.0\"},\n    {\"binaries\": [\"script\"], \"description\": null, \"name\": \"script\", \"root\": \"/home/user/.cargo\", \"version\": null}\n  ]\n}\n"
+        );
+        assert_eq!(
+            super::to_json(&[], &[String::from("{}")]),
+            "{\n  \"errors\": [\n    {}\n  ],\n  \"packages\": []\n}\n"
+        );
+        //Without sources there's no description, only the version cargo recorded.
+        let recorded = crate::PkgInfo {
+            name: String::from("my-script"),
+            version: String::from("0.1.0"),
+            ..Default::default()
+        };
+        let packages = super::packages([("my-script", Some(&recorded), root.clone())]);
+        assert_eq!(packages[0].description, None);
+    }
+}
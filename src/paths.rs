@@ -0,0 +1,93 @@
+//Path handling for install roots. Paths stay PathBufs and are joined component by component, so
+//separators are right on every platform, including UNC (\\server\share) and verbatim (\\?\C:\)
+//paths on Windows, where file names are also compared case-insensitively.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+//Windows doesn't set HOME unless a Unix-like shell does, cargo falls back to USERPROFILE there.
+pub fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE").filter(|_| cfg!(windows)))
+        .map(PathBuf::from)
+}
+
+//Use the platform's separators and drop trailing ones and `.` components, without touching the
+//file system. The prefix (drive letter or UNC share) is kept as is.
+pub fn normalize(path: &Path) -> PathBuf {
+    path.components().collect()
+}
+
+//Whether two paths point to the same place, judging by their names only.
+pub fn same(a: &Path, b: &Path) -> bool {
+    let (a, b) = (normalize(a), normalize(b));
+    if cfg!(windows) {
+        a.to_string_lossy().to_lowercase() == b.to_string_lossy().to_lowercase()
+    } else {
+        a == b
+    }
+}
+
+//The name a binary is known by, without the .exe extension.
+pub fn bin_name(file_name: &str) -> &str {
+    let len = file_name.len();
+    match file_name.get(len.saturating_sub(4)..) {
+        Some(ext) if ext == ".exe" || (cfg!(windows) && ext.eq_ignore_ascii_case(".exe")) => {
+            &file_name[..len - 4]
+        }
+        _ => file_name,
+    }
+}
+
+//Whether a file in a bin directory can be a program: empty files and, on Unix, files without an
+//execute bit are leftovers like interrupted installs or copied build artifacts.
+pub fn is_program(metadata: &fs::Metadata) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if metadata.permissions().mode() & 0o111 == 0 {
+            return false;
+        }
+    }
+    metadata.is_file() && metadata.len() > 0
+}
+
+mod test {
+    #[test]
+    fn normalize() {
+        use std::path::{Path, PathBuf};
+        assert_eq!(
+            super::normalize(Path::new("/home/user/.cargo/./")),
+            PathBuf::from("/home/user/.cargo")
+        );
+        assert!(super::same(
+            Path::new("/home/user/.cargo/"),
+            Path::new("/home//user/.cargo")
+        ));
+        assert!(!super::same(Path::new("/a/b"), Path::new("/a/c")));
+        if cfg!(windows) {
+            assert_eq!(
+                super::normalize(Path::new("C:/Users/user/.cargo")),
+                PathBuf::from(r"C:\Users\user\.cargo")
+            );
+            assert!(super::same(
+                Path::new(r"\\server\share\Cargo"),
+                Path::new(r"\\SERVER\share\cargo\")
+            ));
+        }
+    }
+
+    #[test]
+    fn bin_name() {
+        assert_eq!(super::bin_name("rg.exe"), "rg");
+        assert_eq!(super::bin_name("rg"), "rg");
+        assert_eq!(super::bin_name("exe"), "exe");
+        assert_eq!(super::bin_name("ü.exe"), "ü");
+        assert_eq!(
+            super::bin_name("rg.EXE"),
+            if cfg!(windows) { "rg" } else { "rg.EXE" }
+        );
+    }
+}
@@ -0,0 +1,89 @@
+//A progress bar drawn on stderr while scanning. It's only drawn when stderr is a terminal, so logs
+//and pipes stay clean.
+
+use std::{
+    io::{stderr, IsTerminal, Write},
+    time::{Duration, Instant},
+};
+
+const WIDTH: usize = 30;
+//Redrawing on every step would slow the scan down more than the bar is worth.
+const REDRAW_EVERY: Duration = Duration::from_millis(50);
+
+pub struct Progress {
+    label: &'static str,
+    enabled: bool,
+    done: usize,
+    total: usize,
+    last_draw: Option<Instant>,
+}
+
+impl Progress {
+    pub fn new(label: &'static str, enabled: bool) -> Self {
+        Progress {
+            label,
+            enabled: enabled && stderr().is_terminal(),
+            done: 0,
+            total: 0,
+            last_draw: None,
+        }
+    }
+
+    pub fn hidden() -> Self {
+        Progress::new("", false)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn start(&mut self, total: usize) {
+        self.done = 0;
+        self.total = total;
+    }
+
+    pub fn tick(&mut self) {
+        self.done += 1;
+        if !self.enabled || self.last_draw.is_some_and(|x| x.elapsed() < REDRAW_EVERY) {
+            return;
+        }
+        self.last_draw = Some(Instant::now());
+        eprint!("\r{}", render(self.label, self.done, self.total));
+        let _ = stderr().flush();
+    }
+
+    //Clear the bar, so it doesn't stay above the output.
+    pub fn finish(&mut self) {
+        if self.last_draw.take().is_some() {
+            eprint!("\r\x1b[2K");
+        }
+    }
+}
+
+fn render(label: &str, done: usize, total: usize) -> String {
+    let filled = (done * WIDTH).checked_div(total).unwrap_or(0).min(WIDTH);
+    format!(
+        "{label} [{}{}] {done}/{total}",
+        "=".repeat(filled),
+        " ".repeat(WIDTH - filled)
+    )
+}
+
+mod test {
+    #[test]
+    fn render() {
+        assert_eq!(
+            super::render("manifests", 5, 10),
+            format!("manifests [{}{}] 5/10", "=".repeat(15), " ".repeat(15))
+        );
+        assert_eq!(
+            super::render("manifests", 0, 0),
+            format!("manifests [{}] 0/0", " ".repeat(30))
+        );
+        //Never overflow, even if more items show up than were counted.
+        assert_eq!(
+            super::render("manifests", 11, 10),
+            format!("manifests [{}] 11/10", "=".repeat(30))
+        );
+    }
+}
@@ -0,0 +1,63 @@
+//Starred crates, kept as one binary name per line in the tool's config directory.
+
+use std::{fs, io, path::PathBuf};
+
+pub fn path() -> Option<PathBuf> {
+    Some(crate::config_dir()?.join("starred"))
+}
+
+//Missing or unreadable file means nothing is starred.
+pub fn load() -> Vec<String> {
+    let Some(path) = path() else {
+        return Vec::new();
+    };
+    fs::read_to_string(path)
+        .map(|x| parse(&x))
+        .unwrap_or_default()
+}
+
+fn parse(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+//Star or unstar a crate. Returns false if it already was in the requested state.
+pub fn set(name: &str, starred: bool) -> io::Result<bool> {
+    let Some(path) = path() else {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "failed to locate the config directory",
+        ));
+    };
+    let mut stars = load();
+    if stars.iter().any(|x| x == name) == starred {
+        return Ok(false);
+    }
+    if starred {
+        stars.push(name.to_string());
+        stars.sort();
+    } else {
+        stars.retain(|x| x != name);
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, stars.join("\n") + "\n")?;
+    Ok(true)
+}
+
+mod test {
+    #[test]
+    fn parse() {
+        assert_eq!(
+            super::parse("rg\n\n  just \ncargo-edit\n"),
+            vec!["rg", "just", "cargo-edit"]
+        );
+        assert!(super::parse("").is_empty());
+    }
+}
@@ -0,0 +1,52 @@
+//Did-you-mean suggestions for crate names that aren't installed.
+
+//Number of single character insertions, deletions and substitutions turning a into b.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, x) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, y) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = (diagonal + usize::from(x != *y))
+                .min(above + 1)
+                .min(row[j] + 1);
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+//Up to three names close enough to be a typo, closest first. Longer names allow more typos.
+pub fn closest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+    let max = (name.chars().count() / 3).max(1);
+    let mut close: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .map(|x| (edit_distance(name, x), x))
+        .filter(|(distance, _)| *distance <= max)
+        .collect();
+    close.sort();
+    close.dedup();
+    close.into_iter().take(3).map(|(_, x)| x).collect()
+}
+
+mod test {
+    #[test]
+    fn edit_distance() {
+        assert_eq!(super::edit_distance("ripgrep", "ripgrep"), 0);
+        assert_eq!(super::edit_distance("rg", "rga"), 1);
+        assert_eq!(super::edit_distance("ripgerp", "ripgrep"), 2);
+        assert_eq!(super::edit_distance("", "just"), 4);
+        assert_eq!(super::edit_distance("żółw", "zolw"), 3);
+    }
+
+    #[test]
+    fn closest() {
+        let installed = ["ripgrep", "rg", "rga", "just", "jless", "ripgrep"];
+        assert_eq!(super::closest("ripgerp", installed), vec!["ripgrep"]);
+        assert_eq!(super::closest("rgg", installed), vec!["rg", "rga"]);
+        assert_eq!(super::closest("jest", installed), vec!["just"]);
+        assert!(super::closest("cargo-edit", installed).is_empty());
+    }
+}
@@ -0,0 +1,207 @@
+//Find crates that are also installed through the system package manager, and compare versions,
+//to help settle on one source per tool. Package managers that aren't installed are skipped.
+
+use crate::{
+    i18n::{tr, Lang},
+    version, PkgInfo,
+};
+use colored::Colorize;
+use std::{cmp::Ordering, collections::BTreeMap, process::Command};
+
+//Name, command listing installed packages, and the parser of its output.
+type Manager = (
+    &'static str,
+    &'static [&'static str],
+    fn(&str) -> Vec<(String, String)>,
+);
+
+const MANAGERS: [Manager; 3] = [
+    (
+        "apt",
+        &[
+            "dpkg-query",
+            "--show",
+            "--showformat=${db:Status-Abbrev} ${Package} ${Version}\\n",
+        ],
+        parse_dpkg,
+    ),
+    (
+        "brew",
+        &["brew", "list", "--formula", "--versions"],
+        parse_brew,
+    ),
+    ("pacman", &["pacman", "--query"], parse_brew),
+];
+
+//Lines like `ii  ripgrep 13.0.0-4`, only packages marked as installed count.
+fn parse_dpkg(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|x| {
+            let mut fields = x.split_whitespace();
+            let status = fields.next()?;
+            let (name, version) = (fields.next()?, fields.next()?);
+            (status == "ii").then(|| (name.to_string(), version.to_string()))
+        })
+        .collect()
+}
+
+//Lines like `ripgrep 14.1.0`. Brew lists every installed version, the last one is the newest.
+fn parse_brew(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|x| {
+            let mut fields = x.split_whitespace();
+            let name = fields.next()?;
+            Some((name.to_string(), fields.last()?.to_string()))
+        })
+        .collect()
+}
+
+//Packages installed through each available package manager, by name.
+pub fn installed() -> Vec<(&'static str, BTreeMap<String, String>)> {
+    MANAGERS
+        .iter()
+        .filter_map(|(name, command, parse)| {
+            let output = Command::new(command[0]).args(&command[1..]).output().ok()?;
+            if !output.status.success() {
+                return None;
+            }
+            let packages = parse(&String::from_utf8_lossy(&output.stdout));
+            Some((*name, packages.into_iter().collect()))
+        })
+        .collect()
+}
+
+//Strip what distributions add to the upstream version: the epoch (`1:`), the package revision
+//(`-4`, `_1`) and suffixes like `+dfsg`.
+fn upstream_version(version: &str) -> &str {
+    let version = version.split_once(':').map_or(version, |x| x.1);
+    let end = version
+        .find(|x: char| !(x.is_ascii_digit() || x == '.'))
+        .unwrap_or(version.len());
+    version[..end].trim_end_matches('.')
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Match {
+    pub name: String,
+    pub cargo_version: String,
+    pub manager: &'static str,
+    pub system_version: String,
+}
+
+//Packages are matched by crate name or binary name, distributions use either (fd-find is fd on
+//some of them).
+pub fn compare(
+    installed: &[(&str, &PkgInfo)],
+    system: &[(&'static str, BTreeMap<String, String>)],
+) -> Vec<Match> {
+    let mut matches: Vec<Match> = Vec::new();
+    for (bin, info) in installed {
+        for (manager, packages) in system {
+            let Some(version) = packages.get(&info.name).or_else(|| packages.get(*bin)) else {
+                continue;
+            };
+            if matches
+                .iter()
+                .any(|x| x.name == info.name && x.manager == *manager)
+            {
+                continue;
+            }
+            matches.push(Match {
+                name: info.name.clone(),
+                cargo_version: info.version.clone(),
+                manager,
+                system_version: version.clone(),
+            });
+        }
+    }
+    matches
+}
+
+pub fn print(matches: &[Match], lang: Lang) {
+    if matches.is_empty() {
+        eprintln!("{}", tr(lang, "msg-no-system"));
+    }
+    for x in matches {
+        let relation = match version::compare(
+            upstream_version(&x.system_version),
+            upstream_version(&x.cargo_version),
+        ) {
+            Ordering::Equal => tr(lang, "system-same").green(),
+            Ordering::Greater => tr(lang, "system-newer").yellow(),
+            Ordering::Less => tr(lang, "system-older").yellow(),
+        };
+        println!(
+            "{} {} | {} {} ({relation})",
+            x.name.bold(),
+            x.cargo_version,
+            x.manager.blue(),
+            x.system_version
+        );
+    }
+}
+
+mod test {
+    #[test]
+    fn parse() {
+        assert_eq!(
+            super::parse_dpkg("ii  ripgrep 13.0.0-4\nrc  bat 0.22.1-1\nii  fd-find 8.7.0-3+b1\n"),
+            vec![
+                (String::from("ripgrep"), String::from("13.0.0-4")),
+                (String::from("fd-find"), String::from("8.7.0-3+b1")),
+            ]
+        );
+        assert_eq!(
+            super::parse_brew("ripgrep 13.0.0 14.1.0\njust 1.14.0_1\nbroken\n"),
+            vec![
+                (String::from("ripgrep"), String::from("14.1.0")),
+                (String::from("just"), String::from("1.14.0_1")),
+            ]
+        );
+    }
+
+    #[test]
+    fn upstream_version() {
+        assert_eq!(super::upstream_version("1:2.3.4-1ubuntu1"), "2.3.4");
+        assert_eq!(super::upstream_version("8.7.0+dfsg-3"), "8.7.0");
+        assert_eq!(super::upstream_version("1.14.0_1"), "1.14.0");
+    }
+
+    #[test]
+    fn compare() {
+        let info = |name: &str, version: &str| crate::PkgInfo {
+            name: name.to_string(),
+            version: version.to_string(),
+            ..Default::default()
+        };
+        let (rg, fd, just) = (
+            info("ripgrep", "13.0.0"),
+            info("fd-find", "8.7.0"),
+            info("just", "1.2.3"),
+        );
+        let packages = |x: &[(&str, &str)]| -> std::collections::BTreeMap<String, String> {
+            x.iter()
+                .map(|(x, y)| (x.to_string(), y.to_string()))
+                .collect()
+        };
+        let system = [
+            ("apt", packages(&[("ripgrep", "13.0.0-4")])),
+            ("brew", packages(&[("fd", "9.0.0"), ("ripgrep", "14.1.0")])),
+        ];
+        let matches = super::compare(&[("rg", &rg), ("fd", &fd), ("just", &just)], &system);
+        let found: Vec<(&str, &str, &str)> = matches
+            .iter()
+            .map(|x| (x.name.as_str(), x.manager, x.system_version.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("ripgrep", "apt", "13.0.0-4"),
+                ("ripgrep", "brew", "14.1.0"),
+                ("fd-find", "brew", "9.0.0"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,67 @@
+//How to format for the terminal the output ends up in, following the variables cargo itself reads.
+
+use std::env;
+
+//CARGO_TERM_COLOR, as set for cargo in CI configs and build scripts. "auto" leaves it to NO_COLOR
+//and whether stdout is a terminal.
+pub fn color_override() -> Option<bool> {
+    match env::var("CARGO_TERM_COLOR").ok()?.as_str() {
+        "always" => Some(true),
+        "never" => Some(false),
+        _ => None,
+    }
+}
+
+//The line length to fit the listing into: --width, or COLUMNS, which shells set for their panes.
+//Without either lines aren't shortened, since pipes and logs have no width.
+pub fn width(option: Option<usize>) -> Option<usize> {
+    option
+        .or_else(|| env::var("COLUMNS").ok()?.trim().parse().ok())
+        .filter(|x| *x > 0)
+}
+
+//Length of text as displayed, without the escape sequences colors add.
+pub fn visible_len(s: &str) -> usize {
+    let mut len = 0;
+    let mut chars = s.chars();
+    while let Some(x) = chars.next() {
+        if x == '\x1b' {
+            //Skip to the end of the sequence, like `\x1b[1;32m`.
+            chars.find(|x| x.is_ascii_alphabetic());
+        } else {
+            len += 1;
+        }
+    }
+    len
+}
+
+//Shorten s to at most max characters, marking the cut with an ellipsis.
+pub fn truncate(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        return s.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+    let mut out: String = s.chars().take(max - 1).collect();
+    out.truncate(out.trim_end().len());
+    out + "…"
+}
+
+mod test {
+    #[test]
+    fn visible_len() {
+        assert_eq!(super::visible_len("rg"), 2);
+        assert_eq!(super::visible_len("\x1b[1;32mrg\x1b[0m 13.0.0"), 9);
+        assert_eq!(super::visible_len("★ zażółć"), 8);
+    }
+
+    #[test]
+    fn truncate() {
+        assert_eq!(super::truncate("line-oriented", 20), "line-oriented");
+        assert_eq!(super::truncate("line-oriented", 6), "line-…");
+        assert_eq!(super::truncate("a line", 3), "a…");
+        assert_eq!(super::truncate("zażółć", 4), "zaż…");
+        assert_eq!(super::truncate("text", 0), "");
+    }
+}
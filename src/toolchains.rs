@@ -0,0 +1,77 @@
+//The cargo version each crate was installed with through update, kept as `crate = "version"`
+//lines in the tool's config directory. Cargo records the rustc version itself but not its own,
+//which helps to debug binaries built by old toolchains.
+
+use std::{collections::HashMap, fs, io, path::PathBuf, process::Command};
+
+pub fn path() -> Option<PathBuf> {
+    Some(crate::config_dir()?.join("cargo-versions"))
+}
+
+//Crate name to the output of `cargo --version`. Missing or unreadable file means nothing was
+//recorded.
+pub fn load() -> HashMap<String, String> {
+    let Some(path) = path() else {
+        return HashMap::new();
+    };
+    fs::read_to_string(path)
+        .map(|x| crate::config::parse_pairs(&x).into_iter().collect())
+        .unwrap_or_default()
+}
+
+//The version of the cargo update runs, like `cargo 1.80.0 (376290515 2024-07-16)`.
+pub fn cargo_version() -> Option<String> {
+    let output = Command::new(crate::update::cargo())
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|x| x.status.success())?;
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!version.is_empty()).then_some(version)
+}
+
+fn format(versions: &HashMap<String, String>) -> String {
+    let mut lines: Vec<String> = versions
+        .iter()
+        .map(|(name, version)| format!("{name} = \"{version}\"\n"))
+        .collect();
+    lines.sort();
+    lines.concat()
+}
+
+//Record the cargo version a crate was just installed with.
+pub fn record(name: &str, version: &str) -> io::Result<()> {
+    let Some(path) = path() else {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "failed to locate the config directory",
+        ));
+    };
+    let mut versions = load();
+    versions.insert(name.to_string(), version.to_string());
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, format(&versions))
+}
+
+mod test {
+    #[test]
+    fn format() {
+        let versions = [
+            ("ripgrep", "cargo 1.80.0 (376290515 2024-07-16)"),
+            ("just", "cargo 1.70.0"),
+        ]
+        .into_iter()
+        .map(|(x, y)| (x.to_string(), y.to_string()))
+        .collect();
+        let content = super::format(&versions);
+        assert_eq!(
+            content,
+            "just = \"cargo 1.70.0\"\nripgrep = \"cargo 1.80.0 (376290515 2024-07-16)\"\n"
+        );
+        let parsed: std::collections::HashMap<_, _> =
+            crate::config::parse_pairs(&content).into_iter().collect();
+        assert_eq!(parsed, versions);
+    }
+}
@@ -0,0 +1,215 @@
+//Removing installed crates. Cargo uninstalls what it recorded installing, binaries it has no record
+//of are removed directly, since cargo would refuse to.
+
+use crate::{
+    i18n::{tr, Lang},
+    PkgInfo,
+};
+use colored::Colorize;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Removal {
+    //A crate and the binaries cargo removes with it.
+    Cargo {
+        name: String,
+        root: Option<PathBuf>,
+        bins: Vec<String>,
+    },
+    //A binary cargo has no record of.
+    File(PathBuf),
+}
+
+impl Removal {
+    //What will be done, as a command line.
+    pub fn describe(&self) -> String {
+        match self {
+            Removal::Cargo { name, root, bins } => {
+                let root = root
+                    .as_ref()
+                    .map(|x| format!(" --root {}", x.display()))
+                    .unwrap_or_default();
+                format!("cargo uninstall{root} {name} ({})", bins.join(", "))
+            }
+            Removal::File(path) => format!("rm {}", path.display()),
+        }
+    }
+
+    fn run(&self) -> Result<(), String> {
+        match self {
+            Removal::Cargo { name, root, .. } => {
+                let mut command = Command::new(crate::update::cargo());
+                command.arg("uninstall");
+                if let Some(root) = root {
+                    command.arg("--root").arg(root);
+                }
+                match command.arg(name).status() {
+                    Ok(status) if status.success() => Ok(()),
+                    Ok(status) => Err(status.to_string()),
+                    Err(e) => Err(e.to_string()),
+                }
+            }
+            Removal::File(path) => fs::remove_file(path).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+//Install root a binary is in, it's in the root's bin directory.
+fn root(path: &Path) -> Option<PathBuf> {
+    Some(path.parent()?.parent()?.to_path_buf())
+}
+
+//Metadata of crates cargo recorded installing, which cargo can uninstall.
+fn recorded(info: Option<&PkgInfo>) -> Option<&PkgInfo> {
+    info.filter(|x| !x.source.is_empty())
+}
+
+//What removing each of the named crates or binaries takes. Installed binaries come with their
+//metadata and path, once for every root they're in, and each root gets its own removal. The first
+//name that isn't installed is the error.
+pub fn plan<'a>(
+    names: &'a [String],
+    installed: &[(&str, Option<&PkgInfo>, PathBuf)],
+) -> Result<Vec<Removal>, &'a str> {
+    let mut removals: Vec<Removal> = Vec::new();
+    for name in names {
+        //A crate cargo recorded can be named by any of its binaries.
+        let found: Vec<_> = installed
+            .iter()
+            .filter(|(bin, info, _)| {
+                bin == name || recorded(*info).is_some_and(|x| x.name == *name)
+            })
+            .collect();
+        if found.is_empty() {
+            return Err(name);
+        }
+        for (_, info, path) in found {
+            let removal = match recorded(*info) {
+                Some(info) => Removal::Cargo {
+                    name: info.name.clone(),
+                    root: root(path),
+                    bins: installed
+                        .iter()
+                        .filter(|(_, x, y)| {
+                            recorded(*x).is_some_and(|x| x.name == info.name)
+                                && root(y) == root(path)
+                        })
+                        .map(|(bin, _, _)| bin.to_string())
+                        .collect(),
+                },
+                None => Removal::File(path.clone()),
+            };
+            if !removals.contains(&removal) {
+                removals.push(removal);
+            }
+        }
+    }
+    Ok(removals)
+}
+
+//List what will be removed and ask whether to go on. Anything but yes, including no answer when
+//stdin isn't a terminal, is no.
+pub fn confirm(removals: &[Removal], lang: Lang) -> bool {
+    for x in removals {
+        eprintln!("\t{}", x.describe());
+    }
+    eprint!("{} ", tr(lang, "msg-confirm-uninstall"));
+    let mut answer = String::new();
+    if io::stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+    //Without an answer the next message would go on the same line.
+    if !answer.ends_with('\n') {
+        eprintln!();
+    }
+    //Polish answers start with t, for tak.
+    matches!(
+        answer.trim().to_lowercase().as_str(),
+        "y" | "yes" | "t" | "tak"
+    )
+}
+
+//Remove everything, carrying on after failures. Whether all of it was removed.
+pub fn run(removals: &[Removal], lang: Lang) -> bool {
+    let mut ok = true;
+    for removal in removals {
+        match removal {
+            Removal::Cargo { name, bins, .. } => eprintln!(
+                "{} {name} ({})",
+                tr(lang, "msg-uninstalling").green().bold(),
+                bins.join(", ")
+            ),
+            Removal::File(path) => eprintln!(
+                "{} {}",
+                tr(lang, "msg-removing").green().bold(),
+                path.display()
+            ),
+        }
+        if let Err(e) = removal.run() {
+            eprintln!("{}: {e}", tr(lang, "err-uninstall"));
+            ok = false;
+        }
+    }
+    ok
+}
+
+mod test {
+    #[test]
+    fn plan() {
+        use super::Removal;
+        use std::path::PathBuf;
+        let rg = crate::PkgInfo {
+            name: String::from("ripgrep"),
+            version: String::from("13.0.0"),
+            source: String::from("registry+https://github.com/rust-lang/crates.io-index"),
+            ..Default::default()
+        };
+        let bin = |x: &str| PathBuf::from("/home/user/.cargo/bin").join(x);
+        let installed = [
+            ("rg", Some(&rg), bin("rg")),
+            ("rga", Some(&rg), bin("rga")),
+            ("script", None, bin("script")),
+        ];
+        let names = |x: &[&str]| -> Vec<String> { x.iter().map(|x| x.to_string()).collect() };
+        let ripgrep = Removal::Cargo {
+            name: String::from("ripgrep"),
+            root: Some(PathBuf::from("/home/user/.cargo")),
+            bins: vec![String::from("rg"), String::from("rga")],
+        };
+        assert_eq!(
+            super::plan(&names(&["rg", "ripgrep", "script"]), &installed),
+            Ok(vec![ripgrep.clone(), Removal::File(bin("script"))])
+        );
+        assert_eq!(super::plan(&names(&["rg", "fd"]), &installed), Err("fd"));
+        //Installed in a second root as well.
+        let other = PathBuf::from("/opt/cargo/bin/rg");
+        let both = [installed[0].clone(), ("rg", Some(&rg), other)];
+        assert_eq!(
+            super::plan(&names(&["ripgrep"]), &both),
+            Ok(vec![
+                Removal::Cargo {
+                    name: String::from("ripgrep"),
+                    root: Some(PathBuf::from("/home/user/.cargo")),
+                    bins: vec![String::from("rg")],
+                },
+                Removal::Cargo {
+                    name: String::from("ripgrep"),
+                    root: Some(PathBuf::from("/opt/cargo")),
+                    bins: vec![String::from("rg")],
+                },
+            ])
+        );
+        assert_eq!(
+            ripgrep.describe(),
+            "cargo uninstall --root /home/user/.cargo ripgrep (rg, rga)"
+        );
+        assert_eq!(
+            Removal::File(bin("script")).describe(),
+            "rm /home/user/.cargo/bin/script"
+        );
+    }
+}
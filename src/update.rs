@@ -0,0 +1,171 @@
+//Reinstalling outdated crates with cargo install, one after another, with cargo's output shown as
+//it goes.
+
+use crate::{
+    i18n::{tr, Lang},
+    network::Outdated,
+    toolchains,
+};
+use colored::Colorize;
+use std::{env, ffi::OsString, path::PathBuf, process::Command};
+
+pub struct Update {
+    pub outdated: Outdated,
+    //Features it was installed with, so the new version is built the same way.
+    pub features: Vec<String>,
+    pub no_default_features: bool,
+    pub all_features: bool,
+    //Install root it's in, so the new version replaces the old one.
+    pub root: Option<PathBuf>,
+    //Target triple to build for instead of the host.
+    pub target: Option<String>,
+    //Build with the versions of dependencies the crate was published with.
+    pub locked: bool,
+}
+
+impl Update {
+    //The crate and versions, like `ripgrep 13.0.0 → 14.1.0`, or the version and target when
+    //building for another target.
+    fn describe(&self) -> String {
+        let x = &self.outdated;
+        match &self.target {
+            Some(target) => format!("{} {} ({target})", x.name, x.latest),
+            None => format!("{} {} → {}", x.name, x.installed, x.latest),
+        }
+    }
+}
+
+//Arguments to cargo. --locked unless turned off, like the exports, so the build matches what was
+//published.
+fn args(update: &Update) -> Vec<OsString> {
+    let mut args: Vec<OsString> = vec!["install".into()];
+    if update.locked {
+        args.push("--locked".into());
+    }
+    if let Some(root) = &update.root {
+        args.extend(["--root".into(), root.into()]);
+    }
+    if let Some(target) = &update.target {
+        args.extend(["--target".into(), target.into()]);
+    }
+    if !update.features.is_empty() {
+        args.extend(["--features".into(), update.features.join(",").into()]);
+    }
+    if update.no_default_features {
+        args.push("--no-default-features".into());
+    }
+    if update.all_features {
+        args.push("--all-features".into());
+    }
+    let x = &update.outdated;
+    args.push(format!("{}@{}", x.name, x.latest).into());
+    args
+}
+
+//Cargo sets CARGO when it runs a subcommand, so it's the same cargo the tool was run with.
+pub fn cargo() -> OsString {
+    env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"))
+}
+
+//Install each update, carrying on after failures. The error of each one that failed. The cargo
+//version of each one installed for the host is recorded for the build info.
+pub fn run(updates: &[Update], lang: Lang) -> Vec<Result<(), String>> {
+    let version = toolchains::cargo_version();
+    updates
+        .iter()
+        .map(|update| {
+            let x = &update.outdated;
+            eprintln!(
+                "{} {}",
+                tr(lang, "msg-updating").green().bold(),
+                update.describe()
+            );
+            match Command::new(cargo()).args(args(update)).status() {
+                Ok(status) if status.success() => {
+                    if let Some(version) = version.as_ref().filter(|_| update.target.is_none()) {
+                        if let Err(e) = toolchains::record(&x.name, version) {
+                            eprintln!(
+                                "{}: {} {}: {e}",
+                                tr(lang, "warn-label").yellow(),
+                                tr(lang, "warn-cargo-version"),
+                                x.name
+                            );
+                        }
+                    }
+                    Ok(())
+                }
+                Ok(status) => Err(status.to_string()),
+                Err(e) => Err(e.to_string()),
+            }
+        })
+        .collect()
+}
+
+pub fn print_summary(updates: &[Update], results: &[Result<(), String>], lang: Lang) {
+    eprintln!("{}:", tr(lang, "msg-update-summary").bold());
+    for (update, result) in updates.iter().zip(results) {
+        let line = update.describe();
+        match result {
+            Ok(()) => eprintln!("\t{}: {line}", tr(lang, "msg-updated").green()),
+            Err(e) => eprintln!("\t{}: {line} ({e})", tr(lang, "msg-update-failed").red()),
+        }
+    }
+}
+
+mod test {
+    #[test]
+    fn args() {
+        let mut update = super::Update {
+            outdated: crate::network::Outdated {
+                name: String::from("ripgrep"),
+                installed: String::from("13.0.0"),
+                latest: String::from("14.1.0"),
+            },
+            features: vec![String::from("pcre2"), String::from("simd")],
+            no_default_features: true,
+            all_features: false,
+            root: Some(std::path::PathBuf::from("/home/user/.cargo")),
+            target: None,
+            locked: true,
+        };
+        assert_eq!(
+            super::args(&update),
+            [
+                "install",
+                "--locked",
+                "--root",
+                "/home/user/.cargo",
+                "--features",
+                "pcre2,simd",
+                "--no-default-features",
+                "ripgrep@14.1.0"
+            ]
+        );
+        update.features.clear();
+        update.no_default_features = false;
+        update.root = None;
+        assert_eq!(
+            super::args(&update),
+            ["install", "--locked", "ripgrep@14.1.0"]
+        );
+        assert_eq!(update.describe(), "ripgrep 13.0.0 → 14.1.0");
+        update.target = Some(String::from("x86_64-unknown-linux-musl"));
+        assert_eq!(
+            super::args(&update),
+            [
+                "install",
+                "--locked",
+                "--target",
+                "x86_64-unknown-linux-musl",
+                "ripgrep@14.1.0"
+            ]
+        );
+        assert_eq!(
+            update.describe(),
+            "ripgrep 14.1.0 (x86_64-unknown-linux-musl)"
+        );
+        update.target = None;
+        update.locked = false;
+        assert_eq!(super::args(&update), ["install", "ripgrep@14.1.0"]);
+    }
+}
@@ -0,0 +1,78 @@
+//Comparing version numbers. Pre-releases come before the release they lead up to, like in semver.
+
+use std::cmp::Ordering;
+
+//Compare pre-releases like `beta.10` identifier by identifier: numbers numerically and before
+//words, words alphabetically, and more identifiers after equal ones come later.
+//source: https://semver.org/#spec-item-11
+fn compare_pre(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a.split('.'), b.split('.'));
+    loop {
+        let ordering = match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => match (x.parse::<u64>(), y.parse::<u64>()) {
+                (Ok(x), Ok(y)) => x.cmp(&y),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => x.cmp(y),
+            },
+        };
+        if ordering.is_ne() {
+            return ordering;
+        }
+    }
+}
+
+//Compare dot separated numbers, a missing one counts as 0. Build metadata after '+' is ignored.
+pub fn compare(a: &str, b: &str) -> Ordering {
+    let parts = |x: &str| -> (Vec<u64>, Option<String>) {
+        let x = x.split('+').next().unwrap_or_default();
+        let (numbers, pre) = match x.split_once('-') {
+            Some((numbers, pre)) => (numbers, Some(pre.to_string())),
+            None => (x, None),
+        };
+        let numbers = numbers.split('.').map(|x| x.parse().unwrap_or(0)).collect();
+        (numbers, pre)
+    };
+    let ((a, a_pre), (b, b_pre)) = (parts(a), parts(b));
+    (0..a.len().max(b.len()))
+        .map(|i| a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0)))
+        .find(|x| x.is_ne())
+        .unwrap_or_else(|| match (a_pre, b_pre) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (Some(a), Some(b)) => compare_pre(&a, &b),
+        })
+}
+
+mod test {
+    #[test]
+    fn compare() {
+        use std::cmp::Ordering;
+        assert_eq!(super::compare("13.0.0", "13"), Ordering::Equal);
+        assert_eq!(super::compare("1.10.0", "1.9.2"), Ordering::Greater);
+        assert_eq!(super::compare("0.9", "0.10"), Ordering::Less);
+        assert_eq!(super::compare("1.0.0-beta.1", "1.0.0"), Ordering::Less);
+        assert_eq!(
+            super::compare("1.0.0-beta.2", "1.0.0-beta.1"),
+            Ordering::Greater
+        );
+        assert_eq!(
+            super::compare("1.0.0-beta.10", "1.0.0-beta.2"),
+            Ordering::Greater
+        );
+        assert_eq!(
+            super::compare("1.0.0-alpha", "1.0.0-alpha.1"),
+            Ordering::Less
+        );
+        assert_eq!(
+            super::compare("1.0.0-rc.1", "1.0.0-beta.11"),
+            Ordering::Greater
+        );
+        assert_eq!(super::compare("1.0.0-1", "1.0.0-alpha"), Ordering::Less);
+        assert_eq!(super::compare("1.0.0+build.5", "1.0.0"), Ordering::Equal);
+    }
+}
@@ -0,0 +1,133 @@
+//Paths that couldn't be read while scanning, registries in registry/src among them, and files in
+//bin directories that aren't programs.
+//They're collected instead of silently skipped and reported together, so it's clear why entries
+//are missing on systems with restricted access.
+
+use crate::{
+    i18n::{tr, Lang},
+    json,
+    locale::Style,
+};
+use colored::Colorize;
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+//Paths that couldn't be read while scanning, and files in bin directories that aren't programs.
+#[derive(Debug, Default)]
+pub struct Warnings {
+    errors: Vec<(PathBuf, io::Error)>,
+    not_programs: Vec<PathBuf>,
+    //Every crate from these is missing its details, so they're reported one by one.
+    registries: Vec<(PathBuf, io::Error)>,
+}
+
+impl Warnings {
+    //Missing paths aren't worth a warning, few roots have every directory.
+    pub fn push(&mut self, path: &Path, error: io::Error) {
+        if error.kind() != io::ErrorKind::NotFound {
+            self.errors.push((path.to_path_buf(), error));
+        }
+    }
+
+    //Like Result::ok, but remembers the error.
+    pub fn ok<T>(&mut self, path: &Path, result: io::Result<T>) -> Option<T> {
+        result.map_err(|e| self.push(path, e)).ok()
+    }
+
+    //Paths that couldn't be read, so what's in them is missing.
+    pub fn errors(&self) -> &[(PathBuf, io::Error)] {
+        &self.errors
+    }
+
+    pub fn unreadable_registry(&mut self, path: &Path, error: io::Error) {
+        if error.kind() != io::ErrorKind::NotFound {
+            self.registries.push((path.to_path_buf(), error));
+        }
+    }
+
+    //Registries in `registry/src` that couldn't be read, every crate from them is missing its
+    //details.
+    pub fn registries(&self) -> &[(PathBuf, io::Error)] {
+        &self.registries
+    }
+
+    pub fn not_program(&mut self, path: &Path) {
+        self.not_programs.push(path.to_path_buf());
+    }
+
+    //Files in bin directories that were skipped for being empty or not executable.
+    pub fn not_programs(&self) -> &[PathBuf] {
+        &self.not_programs
+    }
+
+    //An object for each warning, for --json to report them in the document instead of on stderr.
+    pub fn to_json(&self) -> Vec<String> {
+        let entry = |kind: &str, path: &Path, error: Option<&io::Error>| {
+            json::object(vec![
+                ("kind", json::string(kind)),
+                ("path", json::string(&path.to_string_lossy())),
+                (
+                    "error",
+                    error.map_or_else(|| String::from("null"), |x| json::string(&x.to_string())),
+                ),
+            ])
+        };
+        let registries = self
+            .registries
+            .iter()
+            .map(|(path, error)| entry("unreadable-registry", path, Some(error)));
+        let not_programs = self
+            .not_programs
+            .iter()
+            .map(|path| entry("not-program", path, None));
+        let errors = self
+            .errors
+            .iter()
+            .map(|(path, error)| entry("unreadable", path, Some(error)));
+        registries.chain(not_programs).chain(errors).collect()
+    }
+
+    //A line for each unreadable registry and skipped file, there are rarely more than a couple.
+    //Then one line with the number of unreadable paths, or a line for each of them when verbose.
+    pub fn print(&self, verbose: bool, lang: Lang, style: Style) {
+        for (path, error) in &self.registries {
+            let name = path.file_name().unwrap_or(path.as_os_str());
+            eprintln!(
+                "{}: {} {} ({error}), {}",
+                tr(lang, "warn-label").yellow().bold(),
+                tr(lang, "warn-registry"),
+                name.to_string_lossy(),
+                tr(lang, "warn-registry-details")
+            );
+        }
+        for path in &self.not_programs {
+            eprintln!(
+                "{}: {}: {}",
+                tr(lang, "warn-label").yellow().bold(),
+                path.display(),
+                tr(lang, "warn-not-program")
+            );
+        }
+        if self.errors.is_empty() {
+            return;
+        }
+        eprintln!(
+            "{}: {}: {}",
+            tr(lang, "warn-label").yellow().bold(),
+            tr(lang, "warn-unreadable"),
+            style.count(self.errors.len())
+        );
+        if !verbose {
+            eprintln!("{}", tr(lang, "warn-verbose-hint"));
+            return;
+        }
+        for (path, error) in &self.errors {
+            eprintln!("\t{}: {error}", path.display());
+            if error.kind() == io::ErrorKind::InvalidFilename {
+                eprintln!("\t\t{}", tr(lang, "warn-long-path"));
+            }
+        }
+    }
+}
@@ -0,0 +1,751 @@
+//End-to-end tests running the binary against synthetic CARGO_HOME trees and comparing its output
+//with the snapshots in tests/snapshots. After an intended output change, rerun with
+//UPDATE_SNAPSHOTS=1 to rewrite them and review the diff.
+
+use std::{
+    fs,
+    path::PathBuf,
+    process::Command,
+    time::{Duration, UNIX_EPOCH},
+};
+
+const REGISTRY: &str = "registry/src/index.crates.io-6f17d22bba15001f";
+
+//A fake CARGO_HOME in the temp directory, removed when dropped.
+struct Fixture {
+    root: PathBuf,
+}
+
+impl Fixture {
+    fn new(name: &str) -> Self {
+        let root = std::env::temp_dir().join(format!("cargo-ls-crates-e2e-{name}"));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("cargo/bin")).unwrap();
+        Fixture { root }
+    }
+
+    fn cargo_home(&self) -> PathBuf {
+        self.root.join("cargo")
+    }
+
+    fn file(&self, path: &str, content: &str) -> &Self {
+        let path = self.cargo_home().join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+        self
+    }
+
+    //An executable binary in the bin directory, built by rustc 1.70.0 on 2024-01-01.
+    fn bin(&self, name: &str) -> &Self {
+        self.file(
+            &format!("bin/{name}"),
+            "\x7fELF\0rustc version 1.70.0 (90c541806 2023-05-31)\0",
+        );
+        let path = self.cargo_home().join("bin").join(name);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        let file = fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(UNIX_EPOCH + Duration::from_secs(19723 * 86400))
+            .unwrap();
+        self
+    }
+
+    //An extracted package in registry/src. extra is appended to its [package] table.
+    fn package(&self, name: &str, version: &str, extra: &str) -> &Self {
+        self.file(
+            &format!("{REGISTRY}/{name}-{version}/Cargo.toml"),
+            &format!("[package]\nname = \"{name}\"\nversion = \"{version}\"\n{extra}"),
+        )
+    }
+
+    fn lock(&self, name: &str, version: &str, deps: &[(&str, &str)]) -> &Self {
+        let mut lock = String::from("version = 3\n");
+        for (dep, dep_version) in deps.iter().chain([&(name, version)]) {
+            lock += &format!("\n[[package]]\nname = \"{dep}\"\nversion = \"{dep_version}\"\n");
+        }
+        self.file(&format!("{REGISTRY}/{name}-{version}/Cargo.lock"), &lock)
+    }
+
+    //A shell script on the PATH the binary is run with, standing in for a command like curl.
+    #[cfg(unix)]
+    fn command(&self, name: &str, script: &str) -> &Self {
+        use std::os::unix::fs::PermissionsExt;
+        let path = self.root.join("path").join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, format!("#!/bin/sh\n{script}")).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        self
+    }
+
+    //Run the binary with only this fixture visible, and describe the outcome with the fixture's
+    //path replaced by $ROOT and the platform by $OS, so it can be compared to a snapshot.
+    fn run(&self, args: &[&str]) -> String {
+        self.run_with_env(args, &[])
+    }
+
+    fn run_with_env(&self, args: &[&str], vars: &[(&str, &str)]) -> String {
+        let out = Command::new(env!("CARGO_BIN_EXE_cargo-ls-crates"))
+            .arg("ls-crates")
+            .args(args)
+            .env_clear()
+            .env("CARGO_HOME", self.cargo_home())
+            //Only commands the test puts there can be run.
+            .env("PATH", self.root.join("path"))
+            .env("HOME", self.root.join("home"))
+            .env("XDG_CONFIG_HOME", self.root.join("config"))
+            .env("LANG", "C")
+            .env("NO_COLOR", "1")
+            .envs(vars.iter().copied())
+            .output()
+            .unwrap();
+        let text = format!(
+            "exit: {}\n--- stdout\n{}--- stderr\n{}",
+            out.status.code().unwrap_or(-1),
+            String::from_utf8_lossy(&out.stdout),
+            String::from_utf8_lossy(&out.stderr)
+        );
+        let os = format!("os: {} {}", std::env::consts::OS, std::env::consts::ARCH);
+        text.replace(self.root.to_str().unwrap(), "$ROOT")
+            .replace(&os, "os: $OS")
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+//A typical install: a crate whose binary has another name, a multi-line description, a binary
+//installed from a git repository, and a stray dotfile.
+fn toolbox(name: &str) -> Fixture {
+    let fixture = Fixture::new(name);
+    fixture
+        .bin("rg")
+        .bin("just")
+        .bin("jless")
+        .bin("my-script")
+        .file("bin/.crates.toml", "")
+        .package(
+            "ripgrep",
+            "13.0.0",
+            "description = \"ripgrep is a line-oriented search tool\"\nhomepage = \"https://github.com/BurntSushi/ripgrep\"\nrepository = \"https://github.com/BurntSushi/ripgrep\"\ncategories = [\"command-line-utilities\", \"text-processing\"]\n\n[[bin]]\nname = \"rg\"\npath = \"crates/core/main.rs\"\n",
+        )
+        .lock("ripgrep", "13.0.0", &[("memchr", "2.5.0"), ("regex", "1.8.4")])
+        .package(
+            "just",
+            "1.2.3",
+            "description = \"\"\"\n  Just a\n  command runner\"\"\"\nkeywords = [\"make\"]\n",
+        )
+        .package(
+            "jless",
+            "0.9.0",
+            "description = \"A command-line JSON viewer\"\ndocumentation = \"https://jless.io/user-guide\"\ncategories = [\"command-line-utilities\"]\n",
+        )
+        .lock("jless", "0.9.0", &[("memchr", "2.6.0"), ("serde", "1.0.0")])
+        //Installed with `cargo install --git`, its sources aren't in the registry. It's a member of
+        //a workspace it inherits its description from.
+        .file(
+            "git/checkouts/my-script-1a2b3c4d5e6f7a8b/0f1e2d3/Cargo.toml",
+            "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.package]\ndescription = \"Scripts kept in a workspace\"\n",
+        )
+        .file(
+            "git/checkouts/my-script-1a2b3c4d5e6f7a8b/0f1e2d3/crates/my-script/Cargo.toml",
+            "[package]\nname = \"my-script\"\nversion = \"0.1.0\"\ndescription.workspace = true\n",
+        );
+    fixture
+}
+
+//What cargo records for the toolbox.
+const CRATES_TOML: &str = r#"[v1]
+"jless 0.9.0 (registry+https://github.com/rust-lang/crates.io-index)" = ["jless"]
+"just 1.2.3 (registry+https://github.com/rust-lang/crates.io-index)" = ["just"]
+"my-script 0.1.0 (git+https://github.com/user/my-script#0f1e2d3c)" = ["my-script"]
+"ripgrep 13.0.0 (registry+https://github.com/rust-lang/crates.io-index)" = ["rg"]
+"#;
+
+//Serves the web directory of the fixture, by the path of the URL.
+#[cfg(unix)]
+const CURL: &str = "for x; do url=$x; done\nfile=\"${0%/*}/../web/${url#https://*/}\"\n[ -f \"$file\" ] || { echo 'curl: (22) The requested URL returned error: 404' >&2; exit 22; }\n/bin/cat \"$file\"\n";
+
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{name}.txt"));
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "missing snapshot {}, run with UPDATE_SNAPSHOTS=1",
+            path.display()
+        )
+    });
+    assert_eq!(actual, expected, "output differs from snapshot {name}");
+}
+
+#[test]
+fn listing() {
+    let fixture = toolbox("listing");
+    assert_snapshot("listing", &fixture.run(&[]));
+    assert_snapshot("listing_vd", &fixture.run(&["-vd"]));
+    assert_snapshot("listing_plain", &fixture.run(&["-vd", "--plain"]));
+    assert_snapshot("listing_build_info", &fixture.run(&["--build-info"]));
+    assert_snapshot("listing_all_files", &fixture.run(&["--all-files"]));
+    assert_snapshot("listing_paths", &fixture.run(&["-p"]));
+    assert_snapshot("listing_docs", &fixture.run(&["--docs"]));
+    assert_snapshot("listing_docs_plain", &fixture.run(&["--docs", "--plain"]));
+}
+
+#[test]
+fn json() {
+    let fixture = toolbox("json");
+    //A second binary of the same crate goes in the same object.
+    fixture.bin("rga");
+    //Skipped, and reported under errors instead of on stderr.
+    fixture.file("bin/notes.txt", "");
+    let aliases = fixture.root.join("config/cargo-ls-crates/aliases");
+    fs::create_dir_all(aliases.parent().unwrap()).unwrap();
+    fs::write(&aliases, "rga = \"ripgrep\"\n").unwrap();
+    assert_snapshot("json", &fixture.run(&["--json"]));
+    assert_snapshot(
+        "json_starred",
+        &fixture.run(&["--output", "json", "--starred"]),
+    );
+}
+
+#[test]
+fn names() {
+    let fixture = toolbox("names");
+    assert_snapshot("names_crate", &fixture.run(&["-v", "--crate-names"]));
+    assert_snapshot(
+        "names_crate_plain",
+        &fixture.run(&["--plain", "--crate-names"]),
+    );
+    //The last of the two options wins.
+    assert_snapshot("names_bin", &fixture.run(&["--crate-names", "--bin-names"]));
+}
+
+#[test]
+fn aliases() {
+    let fixture = toolbox("aliases");
+    fixture
+        .bin("mt")
+        .package("my-tools", "0.2.0", "description = \"Odds and ends\"\n")
+        .bin("fd")
+        .package(
+            "fd-find",
+            "8.7.0",
+            "description = \"A simple alternative to find\"\n",
+        );
+    //Without .crates.toml fd is found through the built-in aliases, mt isn't found at all.
+    assert_snapshot("aliases_builtin", &fixture.run(&["-v"]));
+
+    let aliases = fixture.root.join("config/cargo-ls-crates/aliases");
+    fs::create_dir_all(aliases.parent().unwrap()).unwrap();
+    fs::write(&aliases, "mt = \"my-tools\"\n").unwrap();
+    assert_snapshot("aliases_custom", &fixture.run(&["-v"]));
+}
+
+#[test]
+fn terminal() {
+    let fixture = toolbox("terminal");
+    assert_snapshot("terminal_width", &fixture.run(&["-vd", "--width", "40"]));
+    assert_snapshot(
+        "terminal_columns",
+        &fixture.run_with_env(&[], &[("COLUMNS", "20")]),
+    );
+    //CARGO_TERM_COLOR wins over NO_COLOR, like it does for cargo.
+    assert_snapshot(
+        "terminal_color",
+        &fixture.run_with_env(&["-v"], &[("CARGO_TERM_COLOR", "always")]),
+    );
+}
+
+#[test]
+fn locale() {
+    let fixture = toolbox("locale");
+    let us = [("LANG", "en_US.UTF-8")];
+    assert_snapshot("locale_us", &fixture.run_with_env(&["--build-info"], &us));
+    assert_snapshot(
+        "locale_iso",
+        &fixture.run_with_env(&["--build-info", "--iso"], &us),
+    );
+    assert_snapshot(
+        "locale_pl",
+        &fixture.run_with_env(&["--build-info", "--plain"], &[("LC_ALL", "pl_PL.UTF-8")]),
+    );
+}
+
+#[test]
+fn records() {
+    let fixture = toolbox("records");
+    //cargo, installed by rustup, shares its name with a crate extracted as a dependency.
+    fixture.bin("cargo").package(
+        "cargo",
+        "0.72.0",
+        "description = \"Cargo, a package manager for Rust.\"\n",
+    );
+    assert_snapshot("records_none", &fixture.run(&["-vd"]));
+
+    fixture.file(".crates.toml", CRATES_TOML);
+    assert_snapshot("records_toml", &fixture.run(&["-vd"]));
+    //Installed from git, so it can't be reinstalled from crates.io.
+    assert_snapshot("records_export", &fixture.run(&["export", "--dockerfile"]));
+
+    //.crates2.json wins, here it also records a crate built from a local path.
+    let registry = "(registry+https://github.com/rust-lang/crates.io-index)";
+    let installs: Vec<String> = [
+        format!("jless 0.9.0 {registry}"),
+        format!("just 1.2.3 {registry}"),
+        String::from("my-script 0.1.0 (git+https://github.com/user/my-script#0f1e2d3c)"),
+        String::from("my-tools 0.2.0 (path+file:///home/user/my-tools)"),
+        format!("ripgrep 13.0.0 {registry}"),
+    ]
+    .iter()
+    .zip(["jless", "just", "my-script", "mt", "rg"])
+    .map(|(key, bin)| format!(r#""{key}":{{"version_req":null,"bins":["{bin}"],"features":[],"all_features":false,"no_default_features":false,"profile":"release","target":"x86_64-unknown-linux-gnu","rustc":"rustc 1.70.0"}}"#))
+    .collect();
+    fixture.bin("mt").file(
+        ".crates2.json",
+        &format!(r#"{{"installs":{{{}}}}}"#, installs.join(",")),
+    );
+    assert_snapshot("records_json", &fixture.run(&["-v"]));
+}
+
+#[test]
+fn wide() {
+    let fixture = toolbox("wide");
+    assert_snapshot("wide", &fixture.run(&["-v", "--wide"]));
+    assert_snapshot("wide_plain", &fixture.run(&["--wide", "--plain"]));
+}
+
+#[test]
+fn source_versions() {
+    let fixture = toolbox("source-versions");
+    fixture
+        .package(
+            "ripgrep",
+            "14.0.0",
+            "description = \"Newer, not installed\"\n\n[[bin]]\nname = \"rg\"\n",
+        )
+        .package(
+            "ripgrep",
+            "12.1.1",
+            "description = \"Older, not installed\"\n\n[[bin]]\nname = \"rg\"\n",
+        );
+    //Without a record of what's installed the newest version is reported.
+    assert_snapshot("source_versions_newest", &fixture.run(&["-vd"]));
+    fixture.file(".crates.toml", CRATES_TOML);
+    assert_snapshot(
+        "source_versions_recorded",
+        &fixture.run(&["-d", "--all-source-versions"]),
+    );
+    assert_snapshot(
+        "source_versions_plain",
+        &fixture.run(&["--plain", "--all-source-versions"]),
+    );
+}
+
+#[test]
+fn stars() {
+    let fixture = toolbox("stars");
+    assert_snapshot("star", &fixture.run(&["star", "rg"]));
+    assert_snapshot("star_not_installed", &fixture.run(&["star", "fd"]));
+    assert_snapshot("star_did_you_mean", &fixture.run(&["star", "jles"]));
+    assert_snapshot("listing_starred", &fixture.run(&["-v", "--starred"]));
+}
+
+#[test]
+fn groups() {
+    let fixture = toolbox("groups");
+    assert_snapshot("groups", &fixture.run(&["groups"]));
+}
+
+#[test]
+fn by_category() {
+    let fixture = toolbox("by-category");
+    fixture.bin("cargo-edit").package(
+        "cargo-edit",
+        "0.12.0",
+        "description = \"Edit Cargo.toml\"\ncategories = [\"development-tools::cargo-plugins\"]\n",
+    );
+    assert_snapshot("by_category", &fixture.run(&["--by-category"]));
+    assert_snapshot(
+        "by_category_pl",
+        &fixture.run(&["--by-category", "--lang", "pl"]),
+    );
+
+    let categories = fixture.root.join("config/cargo-ls-crates/categories");
+    fs::create_dir_all(categories.parent().unwrap()).unwrap();
+    fs::write(&categories, "rg = \"Search\"\nmy-script = \"Scripts\"\n").unwrap();
+    assert_snapshot("by_category_custom", &fixture.run(&["-v", "--by-category"]));
+}
+
+#[test]
+fn check() {
+    let fixture = toolbox("check");
+    fixture.file(
+        "team.toml",
+        "[tools]\nripgrep = \"13\"\njust = \"1.3\"\nfd-find = \"*\"\n",
+    );
+    let manifest = fixture.cargo_home().join("team.toml");
+    assert_snapshot(
+        "check",
+        &fixture.run(&["check", "--against", manifest.to_str().unwrap()]),
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn check_url() {
+    let fixture = toolbox("check-url");
+    let url = "https://example.com/team.toml";
+    fixture.file(
+        "../web/team.toml",
+        "[tools]\nripgrep = \"13\"\njust = \"1.2\"\n",
+    );
+    assert_snapshot(
+        "check_url_no_curl",
+        &fixture.run(&["check", "--against", url]),
+    );
+
+    fixture.command("curl", CURL);
+    let sha256 = "67d92db02a835d9b98ca58ab830272c72869f9db6e43e7b574530af5ede70ee8";
+    assert_snapshot(
+        "check_url",
+        &fixture.run(&["check", "--against", url, "--sha256", sha256]),
+    );
+    let wrong = sha256.replace('6', "0");
+    assert_snapshot(
+        "check_url_checksum",
+        &fixture.run(&["check", "--against", url, "--sha256", &wrong]),
+    );
+    assert_snapshot(
+        "check_url_http",
+        &fixture.run(&["check", "--against", "http://example.com/team.toml"]),
+    );
+}
+
+#[test]
+fn deps() {
+    let fixture = toolbox("deps");
+    assert_snapshot("deps", &fixture.run(&["deps", "rg"]));
+    assert_snapshot("deps_no_lock", &fixture.run(&["deps", "just"]));
+    assert_snapshot("deps_did_you_mean", &fixture.run(&["deps", "ripgerp"]));
+    assert_snapshot(
+        "deps_diff",
+        &fixture.run(&["deps", "--diff", "rg", "jless"]),
+    );
+}
+
+#[test]
+fn export() {
+    let fixture = toolbox("export");
+    assert_snapshot(
+        "export_dockerfile",
+        &fixture.run(&["export", "--dockerfile"]),
+    );
+    assert_snapshot(
+        "export_devcontainer",
+        &fixture.run(&["export", "--devcontainer"]),
+    );
+
+    //Built the way they were, and from the registry they came from.
+    let registry = "(registry+https://github.com/rust-lang/crates.io-index)";
+    fixture
+        .bin("internal")
+        .package("internal", "0.1.0", "description = \"In-house tool\"\n")
+        .file(
+            ".crates2.json",
+            &format!(
+                r#"{{"installs":{{"internal 0.1.0 (sparse+https://crates.example.com/index/)":{{"bins":["internal"]}},"jless 0.9.0 {registry}":{{"bins":["jless"]}},"just 1.2.3 {registry}":{{"bins":["just"],"all_features":true}},"ripgrep 13.0.0 {registry}":{{"bins":["rg"],"features":["pcre2"],"no_default_features":true}}}}}}"#
+            ),
+        );
+    assert_snapshot(
+        "export_recorded_dockerfile",
+        &fixture.run(&["export", "--dockerfile"]),
+    );
+    assert_snapshot(
+        "export_recorded_devcontainer",
+        &fixture.run(&["export", "--devcontainer"]),
+    );
+}
+
+#[test]
+fn cheatsheet() {
+    let fixture = toolbox("cheatsheet");
+    assert_snapshot("cheatsheet_markdown", &fixture.run(&["cheatsheet"]));
+    assert_snapshot("cheatsheet_html", &fixture.run(&["cheatsheet", "--html"]));
+}
+
+#[test]
+fn debug_info() {
+    let fixture = toolbox("debug-info");
+    //Config files that exist are told apart from ones that were never created.
+    fixture.file("../config/cargo-ls-crates/aliases", "rg = \"ripgrep\"\n");
+    assert_snapshot("debug_info", &fixture.run(&["debug-info"]));
+}
+
+#[test]
+fn no_root() {
+    let fixture = Fixture::new("no-root");
+    fs::remove_dir_all(fixture.cargo_home()).unwrap();
+    assert_snapshot("no_root", &fixture.run(&[]));
+}
+
+#[test]
+fn malformed_manifests() {
+    let fixture = toolbox("malformed");
+    fixture
+        .bin("broken")
+        .bin("unterminated")
+        .file(
+            &format!("{REGISTRY}/broken-1.0.0/Cargo.toml"),
+            "[package\nname = ",
+        )
+        .package(
+            "unterminated",
+            "0.1.0",
+            "description = \"\"\"never closed\n",
+        )
+        .bin("empty-bin-name")
+        .package(
+            "empty-bin-name",
+            "0.1.0",
+            "description = \"x\"\n[[bin]]\nname = \"\n",
+        );
+    assert_snapshot("malformed_manifests", &fixture.run(&["-vd"]));
+}
+
+#[test]
+fn not_programs() {
+    let fixture = toolbox("not-programs");
+    fixture
+        .file("bin/interrupted", "")
+        .file("bin/libfoo.rlib", "!<arch>\n");
+    assert_snapshot("not_programs", &fixture.run(&[]));
+    assert_snapshot("not_programs_all_files", &fixture.run(&["--all-files"]));
+}
+
+#[test]
+fn unreadable_paths() {
+    let fixture = toolbox("unreadable");
+    fixture.bin("latin1");
+    //Not UTF-8, so it can't be read as text. Unlike permissions, this holds when running as root.
+    let manifest = fixture
+        .cargo_home()
+        .join(REGISTRY)
+        .join("latin1-1.0.0/Cargo.toml");
+    fs::create_dir_all(manifest.parent().unwrap()).unwrap();
+    fs::write(
+        &manifest,
+        b"[package]\nname = \"latin1\"\ndescription = \"caf\xe9\"\n",
+    )
+    .unwrap();
+    assert_snapshot("unreadable_paths", &fixture.run(&["-v"]));
+    assert_snapshot(
+        "unreadable_paths_verbose",
+        &fixture.run(&["-v", "--verbose"]),
+    );
+    assert_snapshot("unreadable_paths_debug_info", &fixture.run(&["debug-info"]));
+}
+
+//A registry that can't be read doesn't stop the others from being scanned. The error message in
+//the snapshot is Linux's.
+#[cfg(target_os = "linux")]
+#[test]
+fn unreadable_registry() {
+    let fixture = toolbox("unreadable-registry");
+    //Permissions don't stop root, a symlink pointing to itself can't be read by anyone.
+    let registry = fixture
+        .cargo_home()
+        .join("registry/src/mirror.example.com-0123456789abcdef");
+    std::os::unix::fs::symlink(&registry, &registry).unwrap();
+    fixture.file("registry/src/.DS_Store", "");
+    assert_snapshot("unreadable_registry", &fixture.run(&["-v"]));
+}
+
+#[cfg(unix)]
+#[test]
+fn compare_system() {
+    use std::os::unix::fs::PermissionsExt;
+    let fixture = toolbox("compare-system");
+    assert_snapshot("compare_system_none", &fixture.run(&["--compare-system"]));
+
+    let pacman = fixture.root.join("path/pacman");
+    fs::create_dir_all(pacman.parent().unwrap()).unwrap();
+    fs::write(
+        &pacman,
+        "#!/bin/sh\nprintf 'ripgrep 14.1.0-1\\njust 1.2.3-1\\njless 0.8.0-2\\n'\n",
+    )
+    .unwrap();
+    fs::set_permissions(&pacman, fs::Permissions::from_mode(0o755)).unwrap();
+    assert_snapshot("compare_system", &fixture.run(&["--compare-system"]));
+}
+
+#[cfg(unix)]
+#[test]
+fn outdated() {
+    let fixture = toolbox("outdated");
+    fixture.file(".crates.toml", CRATES_TOML);
+    assert_snapshot("outdated_no_curl", &fixture.run(&["--outdated"]));
+
+    //jless isn't on crates.io.
+    fixture.command("curl", CURL);
+    assert_snapshot("outdated_all_failed", &fixture.run(&["--outdated"]));
+    fixture
+        .file(
+            "../web/ri/pg/ripgrep",
+            "{\"name\":\"ripgrep\",\"vers\":\"13.0.0\",\"yanked\":false}\n{\"name\":\"ripgrep\",\"vers\":\"14.1.0\",\"yanked\":false}\n{\"name\":\"ripgrep\",\"vers\":\"14.1.1\",\"yanked\":true}\n",
+        )
+        .file(
+            "../web/ju/st/just",
+            "{\"name\":\"just\",\"vers\":\"1.2.3\",\"yanked\":false}\n",
+        );
+    assert_snapshot("outdated", &fixture.run(&["--outdated"]));
+    //Times vary, only check the lookup is a phase of its own.
+    let timed = fixture.run(&["outdated", "--timing"]);
+    assert!(timed.contains("         network: "), "{timed}");
+}
+
+#[cfg(unix)]
+#[test]
+fn update() {
+    let fixture = toolbox("update");
+    let registry = "(registry+https://github.com/rust-lang/crates.io-index)";
+    fixture
+        .file(
+            ".crates2.json",
+            &format!(
+                r#"{{"installs":{{"jless 0.9.0 {registry}":{{"bins":["jless"]}},"just 1.2.3 {registry}":{{"bins":["just"],"no_default_features":true}},"my-script 0.1.0 (git+https://github.com/user/my-script#0f1e2d3c)":{{"bins":["my-script"]}},"ripgrep 13.0.0 {registry}":{{"bins":["rg"],"features":["pcre2"]}}}}}}"#
+            ),
+        )
+        .file(
+            "../web/ri/pg/ripgrep",
+            "{\"name\":\"ripgrep\",\"vers\":\"14.1.0\",\"yanked\":false}\n",
+        )
+        .file(
+            "../web/ju/st/just",
+            "{\"name\":\"just\",\"vers\":\"1.3.0\",\"yanked\":false}\n",
+        )
+        .file(
+            "../web/jl/es/jless",
+            "{\"name\":\"jless\",\"vers\":\"0.9.0\",\"yanked\":false}\n",
+        )
+        .command("curl", CURL)
+        //Fails to build just.
+        .command(
+            "cargo",
+            "case \"$*\" in --version) echo 'cargo 1.80.0 (376290515 2024-07-16)'; exit;; esac\necho \"cargo $*\"\ncase \"$*\" in *just@*) echo 'error: failed to compile' >&2; exit 101;; esac\n",
+        );
+    assert_snapshot("update", &fixture.run(&["update"]));
+    //Only what was installed has the cargo version recorded.
+    assert_snapshot(
+        "update_build_info",
+        &fixture.run(&["--build-info", "--plain"]),
+    );
+    assert_snapshot("update_selected", &fixture.run(&["update", "rg"]));
+    //Remembered for rg, until it's updated with --locked again.
+    assert_snapshot(
+        "update_no_locked",
+        &fixture.run(&["update", "--no-locked", "rg"]),
+    );
+    assert_snapshot("update_no_locked_remembered", &fixture.run(&["update"]));
+    assert_snapshot("update_locked", &fixture.run(&["update", "--locked", "rg"]));
+    assert_snapshot("update_up_to_date", &fixture.run(&["update", "jless"]));
+    assert_snapshot("update_pattern", &fixture.run(&["update", "r*"]));
+    assert_snapshot("update_not_installed", &fixture.run(&["update", "fd"]));
+    //Installed versions, so nothing is looked up.
+    assert_snapshot(
+        "update_target",
+        &fixture.run(&[
+            "update",
+            "--target",
+            "x86_64-unknown-linux-musl",
+            "rg",
+            "jless",
+        ]),
+    );
+    assert_snapshot(
+        "update_target_no_names",
+        &fixture.run(&["update", "--target", "x86_64-unknown-linux-musl"]),
+    );
+    //The root it builds into is listed like the others.
+    fixture.file("targets/x86_64-unknown-linux-musl/bin/.crates.toml", "");
+    assert_snapshot("update_target_paths", &fixture.run(&["-p"]));
+}
+
+#[cfg(unix)]
+#[test]
+fn uninstall() {
+    let fixture = toolbox("uninstall");
+    //stray isn't in cargo's records.
+    fixture
+        .bin("stray")
+        .file(".crates.toml", CRATES_TOML)
+        .command("cargo", "echo \"cargo $*\"\n");
+    assert_snapshot(
+        "uninstall_dry_run",
+        &fixture.run(&["uninstall", "--dry-run", "rg", "my-script", "stray"]),
+    );
+    assert!(fixture.cargo_home().join("bin/stray").exists());
+    //Deleting a binary cargo has no record of is confirmed first as well.
+    assert_snapshot(
+        "uninstall_file",
+        &fixture.run(&["uninstall", "ripgrep", "stray"]),
+    );
+    assert!(fixture.cargo_home().join("bin/stray").exists());
+    assert_snapshot(
+        "uninstall",
+        &fixture.run(&["uninstall", "--yes", "ripgrep", "stray"]),
+    );
+    assert!(!fixture.cargo_home().join("bin/stray").exists());
+    assert_snapshot("uninstall_no_names", &fixture.run(&["uninstall"]));
+    //What a pattern matches is confirmed first, there's no one to answer here.
+    assert_snapshot("uninstall_pattern", &fixture.run(&["uninstall", "j*"]));
+    assert_snapshot(
+        "uninstall_pattern_yes",
+        &fixture.run(&["uninstall", "--yes", "j*", "just"]),
+    );
+    assert_snapshot(
+        "uninstall_pattern_no_match",
+        &fixture.run(&["uninstall", "fd-*"]),
+    );
+    assert_snapshot(
+        "uninstall_not_installed",
+        &fixture.run(&["uninstall", "jles"]),
+    );
+}
+
+#[test]
+fn info() {
+    let fixture = toolbox("info");
+    fixture.file(
+        ".crates2.json",
+        r#"{"installs":{"ripgrep 13.0.0 (registry+https://github.com/rust-lang/crates.io-index)":{"bins":["rg"],"features":["pcre2","simd"]}}}"#,
+    );
+    assert_snapshot("info", &fixture.run(&["info", "rg"]));
+    assert_snapshot("info_crate_name", &fixture.run(&["info", "ripgrep"]));
+    //Not recorded by cargo, so only what the file tells.
+    assert_snapshot("info_unrecorded", &fixture.run(&["info", "jless"]));
+    assert_snapshot("info_not_installed", &fixture.run(&["info", "rgg"]));
+}
+
+#[cfg(unix)]
+#[test]
+fn man() {
+    use std::os::unix::fs::PermissionsExt;
+    let fixture = toolbox("man");
+    //Without man on the PATH the --help output is shown, and without a terminal it isn't paged.
+    let rg = fixture.cargo_home().join("bin/rg");
+    fs::write(&rg, "#!/bin/sh\necho \"usage: rg [OPTIONS] PATTERN\"\n").unwrap();
+    fs::set_permissions(&rg, fs::Permissions::from_mode(0o755)).unwrap();
+    assert_snapshot("man", &fixture.run(&["man", "rg"]));
+    assert_snapshot("man_crate_name", &fixture.run(&["man", "ripgrep"]));
+    assert_snapshot("man_not_installed", &fixture.run(&["man", "fd"]));
+}